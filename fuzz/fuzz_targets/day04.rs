@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use adv_code_2025::days::day04;
+use adv_code_2025::visualize::Output;
+
+// Any bytes may be rejected with an error, but must never panic.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        day04::validate(text);
+    }
+    let _ = day04::part1(data);
+    let _ = day04::part2(data, Output::default(), false);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use adv_code_2025::days::day06;
+
+// Any bytes may be rejected with an error, but must never panic.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        day06::validate(text);
+    }
+    let _ = day06::part1(data);
+    let _ = day06::part2(data);
+});
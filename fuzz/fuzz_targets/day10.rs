@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use adv_code_2025::days::day10::{self, Backend, Solving};
+
+// Any bytes may be rejected with an error, but must never panic.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        day10::validate(text);
+    }
+    let _ = day10::part1(data, 0, false);
+    let _ = day10::part2(data, Backend::Exact, Solving::default());
+});
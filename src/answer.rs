@@ -0,0 +1,226 @@
+// A puzzle answer that never silently overflows. Integer arithmetic stays on
+// `i128` while it fits and falls back to a `BigInt` when a checked operation
+// fails. Some puzzles answer with text instead; `Solution` holds either kind
+// for printing, and only its numbers support the arithmetic below.
+
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+use num_bigint::BigInt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+}
+
+impl Answer {
+    pub fn zero() -> Self {
+        Answer::Int(0)
+    }
+
+    pub fn one() -> Self {
+        Answer::Int(1)
+    }
+
+    /// Nearest `f64`, for scaling and plotting.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Answer::Int(v) => *v as f64,
+            Answer::Big(v) => v.to_string().parse().unwrap_or(f64::INFINITY),
+        }
+    }
+
+    fn to_big(&self) -> BigInt {
+        match self {
+            Answer::Int(v) => BigInt::from(*v),
+            Answer::Big(v) => v.clone(),
+        }
+    }
+
+    // Keeps values that fit in an `i128` on the fast path.
+    fn normalize(v: BigInt) -> Self {
+        match i128::try_from(&v) {
+            Ok(small) => Answer::Int(small),
+            Err(_) => Answer::Big(v),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Big(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Any puzzle answer: a number, or text such as a code spelled out by the grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Solution {
+    Number(Answer),
+    Text(String),
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Solution::Number(v) => write!(f, "{}", v),
+            Solution::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl<T: Into<Answer>> From<T> for Solution {
+    fn from(v: T) -> Self {
+        Solution::Number(v.into())
+    }
+}
+
+impl From<String> for Solution {
+    fn from(s: String) -> Self {
+        Solution::Text(s)
+    }
+}
+
+impl From<&str> for Solution {
+    fn from(s: &str) -> Self {
+        Solution::Text(s.to_string())
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(v: $t) -> Self {
+                match i128::try_from(v) {
+                    Ok(small) => Answer::Int(small),
+                    Err(_) => Answer::Big(BigInt::from(v)),
+                }
+            }
+        }
+
+        impl PartialEq<Answer> for $t {
+            fn eq(&self, other: &Answer) -> bool {
+                match other {
+                    Answer::Int(v) => i128::try_from(*self).is_ok_and(|s| s == *v),
+                    Answer::Big(v) => BigInt::from(*self) == *v,
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_int!(i32, u32, i64, u64, usize, i128, u128);
+
+impl From<BigInt> for Answer {
+    fn from(v: BigInt) -> Self {
+        Answer::normalize(v)
+    }
+}
+
+impl Add<&Answer> for &Answer {
+    type Output = Answer;
+
+    fn add(self, rhs: &Answer) -> Answer {
+        match (self, rhs) {
+            (Answer::Int(a), Answer::Int(b)) => match a.checked_add(*b) {
+                Some(v) => Answer::Int(v),
+                None => Answer::Big(BigInt::from(*a) + b),
+            },
+            _ => Answer::normalize(self.to_big() + rhs.to_big()),
+        }
+    }
+}
+
+impl Mul<&Answer> for &Answer {
+    type Output = Answer;
+
+    fn mul(self, rhs: &Answer) -> Answer {
+        match (self, rhs) {
+            (Answer::Int(a), Answer::Int(b)) => match a.checked_mul(*b) {
+                Some(v) => Answer::Int(v),
+                None => Answer::Big(BigInt::from(*a) * b),
+            },
+            _ => Answer::normalize(self.to_big() * rhs.to_big()),
+        }
+    }
+}
+
+impl Add for Answer {
+    type Output = Answer;
+
+    fn add(self, rhs: Answer) -> Answer {
+        &self + &rhs
+    }
+}
+
+impl Mul for Answer {
+    type Output = Answer;
+
+    fn mul(self, rhs: Answer) -> Answer {
+        &self * &rhs
+    }
+}
+
+impl AddAssign<&Answer> for Answer {
+    fn add_assign(&mut self, rhs: &Answer) {
+        *self = &*self + rhs;
+    }
+}
+
+impl AddAssign for Answer {
+    fn add_assign(&mut self, rhs: Answer) {
+        *self = &*self + &rhs;
+    }
+}
+
+impl MulAssign<&Answer> for Answer {
+    fn mul_assign(&mut self, rhs: &Answer) {
+        *self = &*self * rhs;
+    }
+}
+
+impl Sum for Answer {
+    fn sum<I: Iterator<Item = Answer>>(iter: I) -> Self {
+        iter.fold(Answer::zero(), |acc, v| acc + v)
+    }
+}
+
+impl Product for Answer {
+    fn product<I: Iterator<Item = Answer>>(iter: I) -> Self {
+        iter.fold(Answer::one(), |acc, v| acc * v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_bigint() {
+        let max = Answer::from(i128::MAX);
+        let sum = &max + &Answer::one();
+        assert!(matches!(sum, Answer::Big(_)));
+        assert_eq!("170141183460469231731687303715884105728", sum.to_string());
+
+        let product: Answer = [u128::MAX, 3].into_iter().map(Answer::from).product();
+        assert_eq!("1020847100762815390390123822295304634365", product.to_string());
+    }
+
+    #[test]
+    fn returns_to_int_when_small() {
+        let big = Answer::from(u128::MAX);
+        assert!(matches!(big, Answer::Big(_)));
+        assert_eq!(Answer::Int(0), big.clone() * Answer::zero());
+        assert!(40 == Answer::from(40u64));
+    }
+
+    #[test]
+    fn text_solutions() {
+        assert_eq!(Solution::Number(Answer::Int(7)), Solution::from(7u32));
+        assert_eq!("EZFCHJAB", Solution::from("EZFCHJAB").to_string());
+        assert_eq!(Solution::Text(String::from("x")), Solution::from(String::from("x")));
+    }
+}
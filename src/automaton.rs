@@ -0,0 +1,302 @@
+// Synchronous cellular automata on a rectangular grid, generalising Day 4's
+// roll removal. Every step computes each cell's next state from its current
+// state and its neighbours' states, then updates all cells at once.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The eight surrounding cells.
+    Moore,
+    /// The four orthogonally adjacent cells.
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Moore => &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
+            Neighbourhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// Cells past the edge do not exist.
+    Bounded,
+    /// The grid is a torus.
+    Wrap,
+}
+
+/// The states of a cell's neighbours.
+#[derive(Debug)]
+pub struct Neighbours<'a, T> {
+    states: &'a [T],
+}
+
+impl<T: PartialEq> Neighbours<'_, T> {
+    pub fn count(&self, state: &T) -> usize {
+        self.states.iter().filter(|s| *s == state).count()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.states.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// A step changed nothing; `steps` is the number of steps that did.
+    Fixpoint { steps: usize },
+    /// The grid after `start` steps comes back every `period` steps.
+    Cycle { start: usize, period: usize },
+    /// The step limit ran out first.
+    Limit,
+}
+
+/// A row whose length differs from the first row's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaggedGrid {
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {} has {} cells, expected {}", self.row + 1, self.found, self.expected)
+    }
+}
+
+impl Error for RaggedGrid {}
+
+type Rule<T> = Box<dyn Fn(&T, &Neighbours<T>) -> T>;
+
+pub struct Automaton<T> {
+    grid: Vec<Vec<T>>,
+    rule: Rule<T>,
+    neighbourhood: Neighbourhood,
+    edges: Edges,
+    generation: usize,
+    history: Option<Vec<Vec<Vec<T>>>>,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    /// A bounded Moore automaton; `rule` maps a cell and its neighbours to the cell's next state.
+    /// Fails unless every row is as long as the first.
+    pub fn new(grid: Vec<Vec<T>>, rule: impl Fn(&T, &Neighbours<T>) -> T + 'static) -> Result<Self, RaggedGrid> {
+        let expected = grid.first().map_or(0, Vec::len);
+        if let Some((row, cells)) = grid.iter().enumerate().find(|(_, cells)| cells.len() != expected) {
+            return Err(RaggedGrid { row, expected, found: cells.len() });
+        }
+        Ok(Self {
+            grid,
+            rule: Box::new(rule),
+            neighbourhood: Neighbourhood::Moore,
+            edges: Edges::Bounded,
+            generation: 0,
+            history: None,
+        })
+    }
+
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    pub fn with_edges(mut self, edges: Edges) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    /// Keeps a copy of the grid after every step, starting with the current one.
+    pub fn with_history(mut self) -> Self {
+        self.history = Some(vec![self.grid.clone()]);
+        self
+    }
+
+    pub fn grid(&self) -> &Vec<Vec<T>> {
+        &self.grid
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The grid after each step, if history is kept.
+    pub fn history(&self) -> Option<&[Vec<Vec<T>>]> {
+        self.history.as_deref()
+    }
+
+    pub fn count(&self, state: &T) -> usize {
+        self.grid.iter().flatten().filter(|s| *s == state).count()
+    }
+
+    fn neighbour(&self, row: usize, col: usize, (dr, dc): (isize, isize)) -> Option<&T> {
+        let (height, width) = (self.grid.len() as isize, self.grid[row].len() as isize);
+        let (mut r, mut c) = (row as isize + dr, col as isize + dc);
+        match self.edges {
+            Edges::Wrap => {
+                r = r.rem_euclid(height);
+                c = c.rem_euclid(width);
+            }
+            Edges::Bounded if r < 0 || c < 0 || r >= height || c >= width => return None,
+            Edges::Bounded => {}
+        }
+        Some(&self.grid[r as usize][c as usize])
+    }
+
+    /// Applies the rule to every cell at once and returns how many cells changed.
+    pub fn step(&mut self) -> usize {
+        let offsets = self.neighbourhood.offsets();
+        let mut states = Vec::with_capacity(offsets.len());
+        let mut changed = 0;
+        let mut next = Vec::with_capacity(self.grid.len());
+        for (row, cells) in self.grid.iter().enumerate() {
+            let mut next_row = Vec::with_capacity(cells.len());
+            for (col, cell) in cells.iter().enumerate() {
+                states.clear();
+                states.extend(offsets.iter().filter_map(|&d| self.neighbour(row, col, d)).cloned());
+                let state = (self.rule)(cell, &Neighbours { states: &states });
+                if state != *cell {
+                    changed += 1;
+                }
+                next_row.push(state);
+            }
+            next.push(next_row);
+        }
+        self.grid = next;
+        self.generation += 1;
+        if let Some(history) = self.history.as_mut() {
+            history.push(self.grid.clone());
+        }
+        changed
+    }
+}
+
+impl<T: Clone + Eq + Hash> Automaton<T> {
+    /// Steps until nothing changes, the grid repeats, or `limit` steps have been taken.
+    /// Repeats are looked up by a 64-bit hash of each grid and confirmed against the grid
+    /// itself, so a hash collision cannot pass for a cycle.
+    pub fn run(&mut self, limit: Option<usize>) -> Outcome {
+        // The grids of this run by step, kept here unless the history already has them.
+        let offset = self.history.as_ref().map_or(0, |history| history.len() - 1);
+        let mut grids = Vec::new();
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut steps = 0;
+        loop {
+            if limit.is_some_and(|limit| steps >= limit) {
+                return Outcome::Limit;
+            }
+            let mut hasher = DefaultHasher::new();
+            self.grid.hash(&mut hasher);
+            let earlier = seen.entry(hasher.finish()).or_default();
+            let stored = |step: usize| match &self.history {
+                Some(history) => &history[offset + step],
+                None => &grids[step],
+            };
+            if let Some(&start) = earlier.iter().find(|&&step| *stored(step) == self.grid) {
+                return Outcome::Cycle { start, period: steps - start };
+            }
+            earlier.push(steps);
+            if self.history.is_none() {
+                grids.push(self.grid.clone());
+            }
+            if self.step() == 0 {
+                return Outcome::Fixpoint { steps };
+            }
+            steps += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn grid(lines: &[&str]) -> Vec<Vec<char>> {
+        lines.iter().map(|line| line.chars().collect()).collect()
+    }
+
+    fn life(cell: &char, n: &Neighbours<char>) -> char {
+        match (cell, n.count(&'#')) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn blinker_cycles() {
+        let mut automaton = Automaton::new(grid(&[".....", "..#..", "..#..", "..#..", "....."]), life).unwrap().with_history();
+        assert_eq!(Outcome::Cycle { start: 0, period: 2 }, automaton.run(Some(10)));
+        assert_eq!(grid(&[".....", ".....", ".###.", ".....", "....."]), automaton.history().unwrap()[1]);
+
+        // A second run starts counting from the grid it finds, with or without history.
+        for keep in [false, true] {
+            let automaton = Automaton::new(grid(&[".....", "..#..", "..#..", "..#..", "....."]), life).unwrap();
+            let mut automaton = if keep { automaton.with_history() } else { automaton };
+            automaton.step();
+            assert_eq!(Outcome::Cycle { start: 0, period: 2 }, automaton.run(None));
+        }
+    }
+
+    proptest! {
+        // Day 4's removal only ever takes rolls away, so it settles within one step per roll.
+        #[test]
+        fn removal_is_monotone(grid in (1usize..12, 1usize..12).prop_flat_map(|(rows, cols)| {
+            prop::collection::vec(prop::collection::vec(prop::bool::weighted(0.6), cols), rows)
+        })) {
+            let grid: Vec<Vec<char>> = grid.iter().map(|row| row.iter().map(|&r| if r { '@' } else { '.' }).collect()).collect();
+            let removal = |cell: &char, n: &Neighbours<char>| if *cell == '@' && n.count(&'@') < 4 { '.' } else { *cell };
+            let rolls = grid.iter().flatten().filter(|&&c| c == '@').count();
+            let mut automaton = Automaton::new(grid, removal).unwrap();
+            loop {
+                let before = automaton.grid().clone();
+                if automaton.step() == 0 {
+                    break;
+                }
+                for (row, prev) in automaton.grid().iter().zip(&before) {
+                    prop_assert!(row.iter().zip(prev).all(|(&c, &p)| c == p || (p, c) == ('@', '.')));
+                }
+                prop_assert!(automaton.generation() <= rolls);
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_and_von_neumann() {
+        // A cell becomes '#' when any neighbour is; across the edge only with wrapping.
+        let spread = |cell: &char, n: &Neighbours<char>| if n.count(&'#') > 0 { '#' } else { *cell };
+        let mut bounded = Automaton::new(grid(&["#..", "...", "..."]), spread).unwrap().with_neighbourhood(Neighbourhood::VonNeumann);
+        bounded.step();
+        assert_eq!(3, bounded.count(&'#'));
+        let mut wrapped = Automaton::new(grid(&["#..", "...", "..."]), spread)
+            .unwrap()
+            .with_neighbourhood(Neighbourhood::VonNeumann)
+            .with_edges(Edges::Wrap);
+        wrapped.step();
+        assert_eq!(5, wrapped.count(&'#'));
+        assert_eq!(Outcome::Fixpoint { steps: 1 }, wrapped.run(None));
+    }
+
+    #[test]
+    fn rejects_ragged_grid() {
+        let err = Automaton::new(grid(&["...", "#", "..."]), life).err().unwrap();
+        assert_eq!(RaggedGrid { row: 1, expected: 3, found: 1 }, err);
+    }
+}
@@ -0,0 +1,524 @@
+// Beam simulation over a character grid, generalising Day 7's tachyon manifold.
+//
+//   S      source; emits a beam downwards (a grid may have several)
+//   .      empty space
+//   ^      splitter; the beam continues in the same direction from both
+//          neighbouring cells perpendicular to it (Day 7's splitter)
+//   - |    splitters; a beam hitting the flat side leaves towards both ends,
+//          a beam along the splitter passes through
+//   / \    mirrors
+//   #      absorber
+//
+// Beams travel in all four directions. Every split doubles the number of
+// timelines, so timelines are counted per state on the (acyclic) state graph;
+// a beam that can come back to a state it already passed through makes that
+// count unbounded and is reported as a loop instead.
+//
+// Grids where beams only ever move down (`S`, `.`, `^` and `#`) can also be
+// solved by `stream_downward`, one line at a time in O(width) memory.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use crate::answer::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (-1, 0),
+            Dir::Down => (1, 0),
+            Dir::Left => (0, -1),
+            Dir::Right => (0, 1),
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Dir::Up | Dir::Down)
+    }
+
+    fn perpendicular(self) -> [Dir; 2] {
+        if self.is_vertical() {
+            [Dir::Left, Dir::Right]
+        } else {
+            [Dir::Up, Dir::Down]
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Source,
+    Splitter,
+    SplitHorizontal,
+    SplitVertical,
+    Mirror,
+    BackMirror,
+    Absorber,
+}
+
+impl Cell {
+    pub fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell::Empty),
+            'S' => Some(Cell::Source),
+            '^' => Some(Cell::Splitter),
+            '-' => Some(Cell::SplitHorizontal),
+            '|' => Some(Cell::SplitVertical),
+            '/' => Some(Cell::Mirror),
+            '\\' => Some(Cell::BackMirror),
+            '#' => Some(Cell::Absorber),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Source => 'S',
+            Cell::Splitter => '^',
+            Cell::SplitHorizontal => '-',
+            Cell::SplitVertical => '|',
+            Cell::Mirror => '/',
+            Cell::BackMirror => '\\',
+            Cell::Absorber => '#',
+        }
+    }
+
+    pub fn is_splitter(self) -> bool {
+        matches!(self, Cell::Splitter | Cell::SplitHorizontal | Cell::SplitVertical)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    UnknownCell { line: usize, column: usize, found: char },
+    Ragged { line: usize, width: usize, expected: usize },
+    /// A cell that could send a beam upwards, which a streaming solver cannot follow.
+    Unsupported { line: usize, column: usize, found: char },
+    /// A beam bounces between adjacent splitters forever.
+    Loop { line: usize, column: usize },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "empty grid"),
+            GridError::UnknownCell { line, column, found } => {
+                write!(f, "line {}, column {}: unknown cell {:?}", line, column, found)
+            }
+            GridError::Ragged { line, width, expected } => {
+                write!(f, "line {}: width {}, expected {}", line, width, expected)
+            }
+            GridError::Unsupported { line, column, found } => {
+                write!(f, "line {}, column {}: {:?} is not supported when streaming", line, column, found)
+            }
+            GridError::Loop { line, column } => write!(f, "line {}, column {}: beam loops forever", line, column),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// A beam occupying a cell and travelling in a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Beam {
+    pub row: usize,
+    pub col: usize,
+    pub dir: Dir,
+}
+
+/// Where a beam leaves the grid: the last cell it was in and the side it left through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Exit {
+    pub row: usize,
+    pub col: usize,
+    pub side: Dir,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Next {
+    Beam(Beam),
+    Exit(Exit),
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    cells: Vec<Vec<Cell>>,
+}
+
+impl Grid {
+    /// Parses a rectangular grid. Blank lines are skipped; line numbers in errors are 1-based.
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self, GridError> {
+        let mut cells: Vec<Vec<Cell>> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.as_ref().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(j, c)| Cell::from_char(c).ok_or(GridError::UnknownCell { line: i + 1, column: j + 1, found: c }))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = cells.first() {
+                if first.len() != row.len() {
+                    return Err(GridError::Ragged { line: i + 1, width: row.len(), expected: first.len() });
+                }
+            }
+            cells.push(row);
+        }
+        if cells.is_empty() {
+            return Err(GridError::Empty);
+        }
+        Ok(Self { cells })
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Cell {
+        self.cells[row][col]
+    }
+
+    /// One downward beam per `S`, in reading order.
+    pub fn sources(&self) -> Vec<Beam> {
+        let mut sources = Vec::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                if cell == Cell::Source {
+                    sources.push(Beam { row, col, dir: Dir::Down });
+                }
+            }
+        }
+        sources
+    }
+
+    fn step(&self, row: usize, col: usize, dir: Dir) -> Option<(usize, usize)> {
+        let (dr, dc) = dir.delta();
+        let r = row.checked_add_signed(dr)?;
+        let c = col.checked_add_signed(dc)?;
+        (r < self.height() && c < self.width()).then_some((r, c))
+    }
+
+    // The beam moves one cell on, keeping `dir` once it gets there.
+    fn advance(&self, row: usize, col: usize, towards: Dir, dir: Dir) -> Next {
+        match self.step(row, col, towards) {
+            Some((row, col)) => Next::Beam(Beam { row, col, dir }),
+            None => Next::Exit(Exit { row, col, side: towards }),
+        }
+    }
+
+    // What a beam turns into after the cell it is in has acted on it.
+    fn next(&self, beam: Beam) -> Vec<Next> {
+        let Beam { row, col, dir } = beam;
+        let go = |d: Dir| self.advance(row, col, d, d);
+        match self.get(row, col) {
+            Cell::Empty | Cell::Source => vec![go(dir)],
+            Cell::Absorber => vec![],
+            Cell::Splitter => dir.perpendicular().iter().map(|&p| self.advance(row, col, p, dir)).collect(),
+            Cell::SplitHorizontal if dir.is_vertical() => vec![go(Dir::Left), go(Dir::Right)],
+            Cell::SplitVertical if !dir.is_vertical() => vec![go(Dir::Up), go(Dir::Down)],
+            Cell::SplitHorizontal | Cell::SplitVertical => vec![go(dir)],
+            Cell::Mirror => vec![go(match dir {
+                Dir::Right => Dir::Up,
+                Dir::Up => Dir::Right,
+                Dir::Left => Dir::Down,
+                Dir::Down => Dir::Left,
+            })],
+            Cell::BackMirror => vec![go(match dir {
+                Dir::Right => Dir::Down,
+                Dir::Down => Dir::Right,
+                Dir::Left => Dir::Up,
+                Dir::Up => Dir::Left,
+            })],
+        }
+    }
+
+    fn splits(&self, beam: Beam) -> bool {
+        match self.get(beam.row, beam.col) {
+            Cell::Splitter => true,
+            Cell::SplitHorizontal => beam.dir.is_vertical(),
+            Cell::SplitVertical => !beam.dir.is_vertical(),
+            _ => false,
+        }
+    }
+
+    pub fn simulate(&self) -> Simulation {
+        self.simulate_from(&self.sources())
+    }
+
+    pub fn simulate_from(&self, sources: &[Beam]) -> Simulation {
+        let mut successors: HashMap<Beam, Vec<Next>> = HashMap::new();
+        let mut finished: HashMap<Beam, bool> = HashMap::new();
+        // Post-order of the reachable states; reversed, a topological order when there is no loop.
+        let mut order = Vec::new();
+        let mut loops = BTreeSet::new();
+
+        for &source in sources {
+            if finished.contains_key(&source) {
+                continue;
+            }
+            finished.insert(source, false);
+            let mut stack = vec![(source, 0)];
+            while let Some((beam, i)) = stack.pop() {
+                let next = successors.entry(beam).or_insert_with(|| self.next(beam)).get(i).copied();
+                match next {
+                    Some(Next::Beam(n)) => {
+                        stack.push((beam, i + 1));
+                        match finished.get(&n) {
+                            None => {
+                                finished.insert(n, false);
+                                stack.push((n, 0));
+                            }
+                            Some(false) => {
+                                loops.insert(n);
+                            }
+                            Some(true) => {}
+                        }
+                    }
+                    Some(Next::Exit(_)) => stack.push((beam, i + 1)),
+                    None => {
+                        finished.insert(beam, true);
+                        order.push(beam);
+                    }
+                }
+            }
+        }
+
+        let mut energized = vec![vec![false; self.width()]; self.height()];
+        let mut activated = BTreeSet::new();
+        for &beam in &order {
+            energized[beam.row][beam.col] = true;
+            if self.splits(beam) {
+                activated.insert((beam.row, beam.col));
+            }
+        }
+
+        let (timelines, cell_timelines) = if loops.is_empty() {
+            let mut counts: HashMap<Beam, Answer> = HashMap::new();
+            for &source in sources {
+                *counts.entry(source).or_insert_with(Answer::zero) += Answer::one();
+            }
+            let mut exits = BTreeMap::new();
+            let mut cells = vec![vec![Answer::zero(); self.width()]; self.height()];
+            for beam in order.iter().rev() {
+                let Some(count) = counts.remove(beam) else { continue };
+                cells[beam.row][beam.col] += &count;
+                for next in &successors[beam] {
+                    let total = match next {
+                        Next::Beam(n) => counts.entry(*n).or_insert_with(Answer::zero),
+                        Next::Exit(e) => exits.entry(*e).or_insert_with(Answer::zero),
+                    };
+                    *total += &count;
+                }
+            }
+            (Some(exits), Some(cells))
+        } else {
+            (None, None)
+        };
+
+        Simulation { energized, activated, loops, timelines, cell_timelines }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// Cells some beam passed through.
+    pub energized: Vec<Vec<bool>>,
+    /// Splitters that split at least one beam, as (row, column).
+    pub activated: BTreeSet<(usize, usize)>,
+    /// States a beam can return to.
+    pub loops: BTreeSet<Beam>,
+    /// Timelines leaving the grid through each exit; `None` if there is a loop.
+    pub timelines: Option<BTreeMap<Exit, Answer>>,
+    /// Timelines passing through each cell, in any direction; `None` if there is a loop.
+    pub cell_timelines: Option<Vec<Vec<Answer>>>,
+}
+
+impl Simulation {
+    /// Total timelines leaving through the given side, or through any side if `None`.
+    pub fn timelines_through(&self, side: Option<Dir>) -> Option<Answer> {
+        let timelines = self.timelines.as_ref()?;
+        Some(
+            timelines
+                .iter()
+                .filter(|(exit, _)| side.is_none_or(|s| exit.side == s))
+                .map(|(_, count)| count.clone())
+                .sum(),
+        )
+    }
+
+    /// Draws the grid with each empty cell replaced by the timelines through it, in columns
+    /// wide enough for the largest count. Cells no beam reaches stay `.`.
+    pub fn render_timelines(&self, grid: &Grid) -> Option<String> {
+        let counts = self.cell_timelines.as_ref()?;
+        let label = |r: usize, c: usize| match grid.get(r, c) {
+            Cell::Empty if counts[r][c] != Answer::zero() => counts[r][c].to_string(),
+            cell => cell.to_char().to_string(),
+        };
+        let width = (0..grid.height())
+            .flat_map(|r| (0..grid.width()).map(move |c| (r, c)))
+            .map(|(r, c)| label(r, c).len())
+            .max()
+            .unwrap_or(1);
+        let separator = if width > 1 { " " } else { "" };
+        let mut out = String::new();
+        for r in 0..grid.height() {
+            let row: Vec<String> = (0..grid.width()).map(|c| format!("{:>width$}", label(r, c))).collect();
+            out.push_str(&row.join(separator));
+            out.push('\n');
+        }
+        Some(out)
+    }
+}
+
+/// Result of [`stream_downward`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Downward {
+    /// Number of splitters that split at least one beam.
+    pub activated: usize,
+    /// Timelines leaving through the bottom of each column.
+    pub columns: Vec<Answer>,
+    /// Sum of `columns`; beams stepping off the sides are lost.
+    pub timelines: Answer,
+}
+
+/// Solves a grid whose beams only move downwards, reading it one line at a time and
+/// keeping only the counts for the current row. Blank lines are skipped.
+pub fn stream_downward<R: BufRead>(reader: R) -> Result<Downward, Box<dyn Error>> {
+    let mut width = None;
+    // Timelines entering each cell of the current row from above.
+    let mut counts: Vec<Answer> = Vec::new();
+    let mut activated = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut cells = Vec::with_capacity(line.len());
+        for (j, c) in line.chars().enumerate() {
+            match Cell::from_char(c) {
+                Some(cell @ (Cell::Empty | Cell::Source | Cell::Splitter | Cell::Absorber)) => cells.push(cell),
+                Some(_) => return Err(GridError::Unsupported { line: i + 1, column: j + 1, found: c }.into()),
+                None => return Err(GridError::UnknownCell { line: i + 1, column: j + 1, found: c }.into()),
+            }
+        }
+        match width {
+            None => {
+                width = Some(cells.len());
+                counts = vec![Answer::zero(); cells.len()];
+            }
+            Some(w) if w != cells.len() => {
+                return Err(GridError::Ragged { line: i + 1, width: cells.len(), expected: w }.into())
+            }
+            Some(_) => {}
+        }
+
+        let mut below = vec![Answer::zero(); cells.len()];
+        for (c, &cell) in cells.iter().enumerate() {
+            if cell == Cell::Source {
+                counts[c] += Answer::one();
+            }
+            if counts[c] == Answer::zero() {
+                continue;
+            }
+            match cell {
+                Cell::Empty | Cell::Source => below[c] += &counts[c],
+                Cell::Splitter => {
+                    activated += 1;
+                    for side in [c.checked_sub(1), Some(c + 1)].into_iter().flatten() {
+                        match cells.get(side) {
+                            Some(Cell::Splitter) => return Err(GridError::Loop { line: i + 1, column: c + 1 }.into()),
+                            Some(Cell::Empty | Cell::Source) => below[side] += &counts[c],
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        counts = below;
+    }
+    if width.is_none() {
+        return Err(GridError::Empty.into());
+    }
+    let timelines = counts.iter().cloned().sum();
+    Ok(Downward { activated, columns: counts, timelines })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_7_example() {
+        let lines = [
+            ".......S.......",
+            "...............",
+            ".......^.......",
+            "...............",
+            "......^.^......",
+            "...............",
+            ".....^.^.^.....",
+            "...............",
+            "....^.^...^....",
+            "...............",
+            "...^.^...^.^...",
+            "...............",
+            "..^...^.....^..",
+            "...............",
+            ".^.^.^.^.^...^.",
+            "...............",
+        ];
+        let sim = Grid::parse(&lines).unwrap().simulate();
+        assert_eq!(21, sim.activated.len());
+        assert!(sim.loops.is_empty());
+        assert_eq!(Some(Answer::from(40)), sim.timelines_through(Some(Dir::Down)));
+
+        let bottom = &sim.cell_timelines.as_ref().unwrap()[15];
+        assert_eq!(40, bottom.iter().cloned().sum::<Answer>());
+        let table = sim.render_timelines(&Grid::parse(&lines).unwrap()).unwrap();
+        assert_eq!(Some(" .  .  .  .  .  .  1  ^  1  .  .  .  .  .  ."), table.lines().nth(2));
+
+        let streamed = stream_downward(lines.join("\n").as_bytes()).unwrap();
+        assert_eq!(21, streamed.activated);
+        assert_eq!(Answer::from(40), streamed.timelines);
+        assert_eq!(bottom, &streamed.columns);
+    }
+
+    #[test]
+    fn mirrors_absorbers_and_loops() {
+        let sim = Grid::parse(&["S.#", "-./", "..."]).unwrap().simulate();
+        // `-` sends one beam off the left edge and one into `/`, which turns it into the absorber.
+        assert_eq!(vec![(1, 0)], sim.activated.iter().copied().collect::<Vec<_>>());
+        let timelines = sim.timelines.unwrap();
+        assert_eq!(1, timelines.len());
+        assert_eq!(Some(&Answer::one()), timelines.get(&Exit { row: 1, col: 0, side: Dir::Left }));
+
+        let sim = Grid::parse(&[".S.", "/-\\", "\\./"]).unwrap().simulate();
+        assert!(!sim.loops.is_empty());
+        assert_eq!(None, sim.timelines);
+        assert_eq!(
+            "line 2, column 2: beam loops forever",
+            stream_downward(".S.\n.^^".as_bytes()).unwrap_err().to_string()
+        );
+        assert_eq!(Err(GridError::UnknownCell { line: 1, column: 2, found: 'x' }), Grid::parse(&["Sx"]).map(|_| ()));
+    }
+}
@@ -1,9 +1,11 @@
 use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::{self, File};
+use std::io::BufReader;
 use code_timing_macros::time_snippet;
 use const_format::concatcp;
 use adv_code_2025::*;
+use adv_code_2025::{diff, gen};
+use adv_code_2025::days::day05::{part1, part2, reference_part2};
 
 const DAY: &str = "05";
 const INPUT_FILE: &str = concatcp!("input/", DAY, ".txt");
@@ -25,33 +27,21 @@ const TEST: &str = "\
 fn main() -> Result<(), Box<dyn Error>> {
     start_day(DAY);
 
+    if let Some(config) = diff::Config::from_args()? {
+        return diff::quiet_panics(|| {
+            diff::check(
+                &config,
+                |rng, size| gen::day05(rng, 4 * size, 6 * size, 20 * size as u64),
+                |input| part2(input.as_bytes()),
+                reference_part2,
+            )
+        });
+    }
+
+    validate::check(&fs::read_to_string(INPUT_FILE)?, days::day05::validate)?;
+
     //region Part 1
     println!("=== Part 1 ===");
-
-    fn part1<R: BufRead>(reader: R) -> Result<u128, Box<dyn Error>> {
-        let mut seen_empty_line= false;
-        let mut v = vec![];
-        let mut ans = 0;
-        for line in reader.lines() {
-            let line = line?;
-            if line == "" {
-                seen_empty_line = true;
-                continue;
-            }
-            if seen_empty_line {
-                let id: u128 = line.parse()?;
-                if is_fresh(&v, id) {
-                    ans += 1;
-                }
-            } else {
-                let chars: Vec<_> = line.trim().split("-").collect();
-                let s = chars.get(0).unwrap().parse::<u128>()?;
-                let e = chars.get(1).unwrap().parse::<u128>()?;
-                v.push((s, e));
-            }
-        }
-        Ok(ans)
-    }
     assert_eq!(3, part1(BufReader::new(TEST.as_bytes()))?);
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
@@ -61,39 +51,6 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     //region Part 2
     println!("\n=== Part 2 ===");
-    fn part2<R: BufRead>(reader: R) -> Result<u128, Box<dyn Error>> {
-        let mut v = vec![];
-        for line in reader.lines() {
-            let line = line?;
-            if line == "" {
-                break;
-            }
-            let chars: Vec<_> = line.trim().split("-").collect();
-            let s = chars.get(0).unwrap().parse::<u128>()?;
-            let e = chars.get(1).unwrap().parse::<u128>()?;
-            v.push((s, e));
-        }
-        v.sort();
-        let mut merged = vec![];
-        for (s, e) in v.iter() {
-            if merged.is_empty() {
-                merged.push((*s, *e));
-            } else {
-                let last = merged.pop().unwrap();
-                if last.0 <= *s && *s <= last.1 {
-                    merged.push((last.0, std::cmp::max(last.1, *e)));
-                } else {
-                    merged.push(last);
-                    merged.push((*s, *e));
-                }
-            }
-        }
-        let mut ans = 0;
-        for (s, e) in merged {
-            ans += e - s + 1;
-        }
-        Ok(ans)
-    }
     assert_eq!(14, part2(BufReader::new(TEST.as_bytes()))?);
 
     let input_file = BufReader::new(File::open(INPUT_FILE)?);
@@ -103,13 +60,3 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
-
-fn is_fresh(ranges: &Vec<(u128, u128)>, id: u128) -> bool {
-    for range in ranges {
-        let (s, e) = range;
-        if *s <= id && id <= *e {
-            return true;
-        }
-    }
-    false
-}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+use clap::{Parser, Subcommand};
+use adv_code_2025::days::{self, Day};
+use adv_code_2025::runner::{self, Job};
+use adv_code_2025::validate;
+
+/// Tools that work across days.
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Checks inputs against their day's invariants and lists every violation.
+    Validate {
+        /// Days to check, e.g. 09; every day with an input file by default.
+        days: Vec<String>,
+        /// Check this file instead of input/NN.txt. Needs exactly one day.
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Solves both parts of several days at once, printing the answers in day order.
+    Run {
+        /// Days to run, e.g. 09; every day with an input file by default.
+        days: Vec<String>,
+        /// Worker threads; one per CPU by default.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+}
+
+fn selected(names: &[String]) -> Result<Vec<&'static Day>, Box<dyn Error>> {
+    if names.is_empty() {
+        return Ok(days::DAYS.iter().filter(|day| fs::exists(day.input_file()).unwrap_or(false)).collect());
+    }
+    names
+        .iter()
+        .map(|name| days::find(name).ok_or_else(|| format!("no solution for day {}", name).into()))
+        .collect()
+}
+
+fn validate_inputs(names: &[String], input: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let days = selected(names)?;
+    if input.is_some() && days.len() != 1 {
+        return Err("--input needs exactly one day".into());
+    }
+    let mut invalid = 0;
+    for day in days {
+        let path = input.clone().unwrap_or_else(|| PathBuf::from(day.input_file()));
+        let violations = (day.validate)(&fs::read_to_string(&path)?);
+        if violations.is_empty() {
+            println!("Day {}: {} is valid", day.day, path.display());
+            continue;
+        }
+        invalid += 1;
+        println!("Day {}: {} has {} problem{}", day.day, path.display(), violations.len(), if violations.len() == 1 { "" } else { "s" });
+        for violation in violations {
+            println!("  {}", violation);
+        }
+    }
+    match invalid {
+        0 => Ok(()),
+        n => Err(format!("{} invalid input{}", n, if n == 1 { "" } else { "s" }).into()),
+    }
+}
+
+// One line of output, and whether it reports a failure.
+type Report = (String, bool);
+
+fn run(names: &[String], threads: Option<usize>) -> Result<(), Box<dyn Error>> {
+    let days = selected(names)?;
+    let inputs = days.iter().map(|day| fs::read_to_string(day.input_file())).collect::<Result<Vec<_>, _>>()?;
+    let threads = threads.unwrap_or_else(runner::threads);
+    let start = Instant::now();
+
+    let mut jobs: Vec<Job<Report>> = Vec::new();
+    let mut labels = Vec::new();
+    for (day, input) in days.iter().zip(&inputs) {
+        if let Err(invalid) = validate::check(input, day.validate) {
+            let report = format!("Day {}: {}", day.day, invalid);
+            jobs.push(Box::new(move || (report, true)));
+            labels.push(format!("Day {}", day.day));
+            continue;
+        }
+        for (n, part) in [(1, day.part1), (2, day.part2)] {
+            jobs.push(Box::new(move || {
+                let start = Instant::now();
+                let result = part(input);
+                let elapsed = start.elapsed();
+                match result {
+                    Ok(answer) => (format!("Day {} part {}: {} ({:.2?})", day.day, n, answer, elapsed), false),
+                    Err(e) => (format!("Day {} part {}: error: {}", day.day, n, e), true),
+                }
+            }));
+            labels.push(format!("Day {} part {}", day.day, n));
+        }
+    }
+
+    let parts = jobs.len();
+    let mut failed = 0;
+    runner::run_ordered(jobs, threads, |i, outcome| {
+        let (line, failure) = outcome.unwrap_or_else(|message| (format!("{}: panicked: {}", labels[i], message), true));
+        println!("{}", line);
+        failed += failure as usize;
+    });
+    println!("{} jobs on {} threads in {:.2?}", parts, threads, start.elapsed());
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} jobs failed", n, parts).into()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match Args::parse().command {
+        Command::Validate { days, input } => validate_inputs(&days, input),
+        Command::Run { days, jobs } => run(&days, jobs),
+    }
+}
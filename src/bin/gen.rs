@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use clap::Parser;
+use adv_code_2025::gen::{self, GenRng};
+
+/// Prints a random input for a day. `--size 1` is about as big as the day's example; real
+/// inputs are usually around `--size 10` to `--size 100`.
+#[derive(Parser)]
+struct Args {
+    /// Day to generate input for, e.g. 04.
+    day: String,
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[arg(long, default_value_t = 1)]
+    size: usize,
+    /// Write to this file instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn generate(day: u32, rng: &mut GenRng, size: usize) -> Option<String> {
+    Some(match day {
+        4 => gen::day04(rng, 10 * size, 10 * size, 0.6),
+        5 => gen::day05(rng, 4 * size, 6 * size, 20 * size.pow(3) as u64),
+        6 => gen::day06(rng, 4 * size, 3 + size / 10, 3),
+        7 => gen::day07(rng, 15 * size, 7 * size, 0.4),
+        8 => gen::day08(rng, 20 * size, 1000 * size as i64),
+        9 => gen::day09(rng, 4 * size, 12 * size as i64),
+        10 => gen::day10(rng, 3 * size, 6 + size / 20, 6 + size / 10, 15),
+        11 => gen::day11(rng, 10 * size, 3),
+        _ => return None,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let day: u32 = args.day.parse()?;
+    let text = generate(day, &mut gen::rng(args.seed), args.size.max(1))
+        .ok_or_else(|| format!("no generator for day {}", args.day))?;
+    match args.output {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
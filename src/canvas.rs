@@ -0,0 +1,249 @@
+// Pictures of puzzle states: a canvas of shapes in cell units that is written
+// as SVG, or rasterised to PPM or PNG. Days describe what to draw in a closure
+// passed to `export`, which only runs it when given a path; the extension of
+// the path picks the format.
+
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GREY: Rgb = Rgb(200, 200, 200);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(60, 170, 80);
+    pub const BLUE: Rgb = Rgb(50, 90, 220);
+    pub const YELLOW: Rgb = Rgb(240, 200, 40);
+
+    /// A heat-map colour from dark blue at 0 through red to yellow at 1.
+    pub fn heat(t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgb(channel(t * 2.0), channel(t * 2.0 - 1.0), channel(0.5 - t))
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Rect { x: f64, y: f64, w: f64, h: f64, fill: Option<Rgb>, stroke: Option<Rgb> },
+    Line { from: (f64, f64), to: (f64, f64), colour: Rgb },
+    /// A closed outline through `points`.
+    Polygon { points: Vec<(f64, f64)>, colour: Rgb },
+    /// Only drawn in SVG; raster formats have no fonts.
+    Text { x: f64, y: f64, text: String, colour: Rgb },
+}
+
+/// A picture `width` by `height` units large, rasterised at `scale` pixels per unit.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub width: f64,
+    pub height: f64,
+    pub scale: u32,
+    pub background: Rgb,
+    shapes: Vec<Shape>,
+}
+
+impl Canvas {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self { width, height, scale: 8, background: Rgb::WHITE, shapes: Vec::new() }
+    }
+
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn add(&mut self, shape: Shape) -> &mut Self {
+        self.shapes.push(shape);
+        self
+    }
+
+    /// One filled unit square per cell, row by row; `None` leaves the background.
+    pub fn cells(&mut self, colours: &[Vec<Option<Rgb>>]) -> &mut Self {
+        for (r, row) in colours.iter().enumerate() {
+            for (c, colour) in row.iter().enumerate() {
+                if let Some(&fill) = colour.as_ref() {
+                    self.add(Shape::Rect { x: c as f64, y: r as f64, w: 1.0, h: 1.0, fill: Some(fill), stroke: None });
+                }
+            }
+        }
+        self
+    }
+
+    pub fn to_svg(&self) -> String {
+        let s = self.scale as f64;
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            self.width * s,
+            self.height * s,
+            self.width,
+            self.height
+        );
+        let _ = writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", self.background.hex());
+        let paint = |c: Option<Rgb>| c.map_or(String::from("none"), Rgb::hex);
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Rect { x, y, w, h, fill, stroke } => writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"0.2\"/>",
+                    x, y, w, h, paint(*fill), paint(*stroke)
+                ),
+                Shape::Line { from, to, colour } => writeln!(
+                    out,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"0.2\"/>",
+                    from.0, from.1, to.0, to.1, colour.hex()
+                ),
+                Shape::Polygon { points, colour } => {
+                    let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+                    writeln!(
+                        out,
+                        "<polygon points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.2\"/>",
+                        points.join(" "),
+                        colour.hex()
+                    )
+                }
+                Shape::Text { x, y, text, colour } => writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"0.6\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text>",
+                    x, y, colour.hex(), text
+                ),
+            };
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    fn pixel_size(&self) -> (usize, usize) {
+        let s = self.scale as f64;
+        ((self.width * s).ceil() as usize, (self.height * s).ceil() as usize)
+    }
+
+    /// The picture as rows of pixels.
+    pub fn rasterise(&self) -> Vec<Vec<Rgb>> {
+        let (w, h) = self.pixel_size();
+        let s = self.scale as f64;
+        let mut pixels = vec![vec![self.background; w]; h];
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect { x, y, w: rw, h: rh, fill, stroke } => {
+                    if let Some(fill) = *fill {
+                        let (x0, y0) = ((x * s).round() as usize, (y * s).round() as usize);
+                        let (x1, y1) = (((x + rw) * s).round() as usize, ((y + rh) * s).round() as usize);
+                        for row in pixels.iter_mut().take(y1.min(h)).skip(y0) {
+                            for p in row.iter_mut().take(x1.min(w)).skip(x0) {
+                                *p = fill;
+                            }
+                        }
+                    }
+                    if let Some(stroke) = *stroke {
+                        // Keep the right and bottom edges inside the rectangle.
+                        let (x1, y1) = (x + rw - 0.5 / s, y + rh - 0.5 / s);
+                        let corners = [(*x, *y), (x1, *y), (x1, y1), (*x, y1)];
+                        for i in 0..4 {
+                            draw_line(&mut pixels, s, corners[i], corners[(i + 1) % 4], stroke);
+                        }
+                    }
+                }
+                Shape::Line { from, to, colour } => draw_line(&mut pixels, s, *from, *to, *colour),
+                Shape::Polygon { points, colour } => {
+                    for i in 0..points.len() {
+                        draw_line(&mut pixels, s, points[i], points[(i + 1) % points.len()], *colour);
+                    }
+                }
+                Shape::Text { .. } => {}
+            }
+        }
+        pixels
+    }
+
+    /// Binary PPM (P6).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (w, h) = self.pixel_size();
+        let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+        for p in self.rasterise().into_iter().flatten() {
+            out.extend([p.0, p.1, p.2]);
+        }
+        out
+    }
+
+    pub fn write_png(&self, path: &Path) -> io::Result<()> {
+        let (w, h) = self.pixel_size();
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), w as u32, h as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data: Vec<u8> = self.rasterise().into_iter().flatten().flat_map(|p| [p.0, p.1, p.2]).collect();
+        encoder.write_header().and_then(|mut writer| writer.write_image_data(&data)).map_err(io::Error::other)
+    }
+
+    /// Writes SVG, PPM or PNG depending on the extension of `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => fs::write(path, self.to_svg()),
+            Some("ppm") => fs::write(path, self.to_ppm()),
+            Some("png") => self.write_png(path),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: expected a .svg, .ppm or .png file", path.display()),
+            )),
+        }
+    }
+}
+
+// Samples the segment at least twice per pixel.
+fn draw_line(pixels: &mut [Vec<Rgb>], scale: f64, from: (f64, f64), to: (f64, f64), colour: Rgb) {
+    let steps = ((to.0 - from.0).abs().max((to.1 - from.1).abs()) * scale * 2.0).ceil().max(1.0) as usize;
+    for i in 0..=steps {
+        let t = i as f64 / steps as f64;
+        let px = ((from.0 + (to.0 - from.0) * t) * scale).floor();
+        let py = ((from.1 + (to.1 - from.1) * t) * scale).floor();
+        if px >= 0.0 && py >= 0.0 {
+            if let Some(p) = pixels.get_mut(py as usize).and_then(|row| row.get_mut(px as usize)) {
+                *p = colour;
+            }
+        }
+    }
+}
+
+/// Draws the picture and saves it to `path`, if there is one.
+pub fn export(path: Option<&Path>, draw: impl FnOnce() -> Canvas) -> io::Result<()> {
+    match path {
+        Some(path) => draw().save(path),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raster_and_vector() {
+        let mut canvas = Canvas::new(2.0, 1.0).with_scale(2);
+        canvas.cells(&[vec![Some(Rgb::RED), None]]);
+        canvas.add(Shape::Line { from: (1.0, 0.0), to: (1.0, 1.0), colour: Rgb::BLUE });
+
+        let pixels = canvas.rasterise();
+        assert_eq!(vec![Rgb::RED, Rgb::RED, Rgb::BLUE, Rgb::WHITE], pixels[0]);
+        assert_eq!(b"P6\n4 2\n255\n", &canvas.to_ppm()[..11]);
+        assert_eq!(11 + 4 * 2 * 3, canvas.to_ppm().len());
+
+        let svg = canvas.to_svg();
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#dc2828\""));
+        assert!(svg.contains("<line x1=\"1\" y1=\"0\" x2=\"1\" y2=\"1\" stroke=\"#325adc\""));
+
+        let path = std::env::temp_dir().join(format!("canvas-{}.png", std::process::id()));
+        canvas.save(&path).unwrap();
+        assert_eq!(b"\x89PNG", &fs::read(&path).unwrap()[..4]);
+        fs::remove_file(path).unwrap();
+        assert!(canvas.save(Path::new("picture.gif")).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+// Day 4: paper rolls, removed while fewer than four rolls surround them.
+
+use std::error::Error;
+use std::io::BufRead;
+use crate::automaton::{Automaton, Outcome, RaggedGrid};
+use crate::canvas::{self, Canvas, Rgb};
+use crate::validate::Violation;
+use crate::visualize::{Colour, Output, Visualizer};
+
+fn parse<R: BufRead>(reader: R) -> Result<Vec<Vec<char>>, Box<dyn Error>> {
+    let mut grid: Vec<Vec<char>> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let row: Vec<char> = line?.chars().collect();
+        if let Some(first) = grid.first() {
+            if row.len() != first.len() {
+                return Err(format!("line {}: expected {} cells, found {}", i + 1, first.len(), row.len()).into());
+            }
+        }
+        grid.push(row);
+    }
+    if grid.is_empty() {
+        return Err("the grid is empty".into());
+    }
+    Ok(grid)
+}
+
+/// Rows of `@` and `.`, all as wide as the first.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut width = None;
+    for (i, line) in input.lines().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            if c != '@' && c != '.' {
+                violations.push(Violation::at(i + 1, j + 1, format!("unexpected {:?}, expected '@' or '.'", c)));
+            }
+        }
+        let cells = line.chars().count();
+        match width {
+            None => width = Some(cells),
+            Some(expected) if cells != expected => {
+                violations.push(Violation::line(i + 1, format!("expected {} cells, found {}", expected, cells)))
+            }
+            _ => {}
+        }
+    }
+    if width.is_none() {
+        violations.push(Violation::input("the grid is empty"));
+    }
+    violations
+}
+
+/// Each step removes every roll with fewer than four rolls around it.
+fn removal(grid: Vec<Vec<char>>) -> Result<Automaton<char>, RaggedGrid> {
+    Automaton::new(grid, |&cell, neighbours| {
+        if cell == '@' && neighbours.count(&'@') < 4 { '.' } else { cell }
+    })
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<i32, Box<dyn Error>> {
+    Ok(removal(parse(reader)?)?.step() as i32)
+}
+
+/// With `show_rounds`, also prints the round in which each roll was removed.
+pub fn part2<R: BufRead>(reader: R, out: Output, show_rounds: bool) -> Result<i32, Box<dyn Error>> {
+    let grid = parse(reader)?;
+    let rounds = removal_rounds(&grid);
+    if show_rounds {
+        print!("{}", render_rounds(&grid, &rounds));
+    }
+    if let Some(vis) = out.visualizer {
+        let mut vis = vis.with_palette(&[('@', Colour::Yellow), ('x', Colour::Red), ('.', Colour::Grey)]);
+        animate_rounds(&mut vis, &grid, &rounds)?;
+    }
+    canvas::export(out.export.as_deref(), || heat_map(&grid, &rounds))?;
+    Ok(rounds.iter().flatten().filter(|round| round.is_some()).count() as i32)
+}
+
+pub fn part2_automaton<R: BufRead>(reader: R) -> Result<i32, Box<dyn Error>> {
+    let mut automaton = removal(parse(reader)?)?;
+    let rolls = automaton.count(&'@');
+    match automaton.run(None) {
+        Outcome::Fixpoint { .. } => Ok((rolls - automaton.count(&'@')) as i32),
+        outcome => Err(format!("removal did not settle: {:?}", outcome).into()),
+    }
+}
+
+/// Round (from 1) in which each roll is removed, or `None` for cells that are never removed.
+/// Matches running `removal` to a fixpoint, but only revisits rolls next to a removed one.
+fn removal_rounds(grid: &[Vec<char>]) -> Vec<Vec<Option<usize>>> {
+    let row_size = grid.len();
+    let col_size = grid[0].len();
+    let neighbours = |row: usize, col: usize| {
+        (-1i32..=1)
+            .flat_map(move |dr| (-1i32..=1).map(move |dc| (row as i32 + dr, col as i32 + dc)))
+            .filter(move |&(r, c)| {
+                (r, c) != (row as i32, col as i32) && r >= 0 && c >= 0 && r < row_size as i32 && c < col_size as i32
+            })
+            .map(|(r, c)| (r as usize, c as usize))
+    };
+
+    let mut counts = vec![vec![0; col_size]; row_size];
+    let mut rounds = vec![vec![None; col_size]; row_size];
+    let mut queue = Vec::new();
+    for row in 0..row_size {
+        for col in 0..col_size {
+            if grid[row][col] != '@' {
+                continue;
+            }
+            counts[row][col] = neighbours(row, col).filter(|&(r, c)| grid[r][c] == '@').count();
+            if counts[row][col] < 4 {
+                rounds[row][col] = Some(1);
+                queue.push((row, col));
+            }
+        }
+    }
+
+    let mut round = 1;
+    while !queue.is_empty() {
+        let mut next = Vec::new();
+        for (row, col) in queue {
+            for (r, c) in neighbours(row, col) {
+                if grid[r][c] != '@' || rounds[r][c].is_some() {
+                    continue;
+                }
+                counts[r][c] -= 1;
+                if counts[r][c] < 4 {
+                    rounds[r][c] = Some(round + 1);
+                    next.push((r, c));
+                }
+            }
+        }
+        queue = next;
+        round += 1;
+    }
+    rounds
+}
+
+fn render_rounds(grid: &[Vec<char>], rounds: &[Vec<Option<usize>>]) -> String {
+    let label = |row: usize, col: usize| match rounds[row][col] {
+        Some(round) => round.to_string(),
+        None => grid[row][col].to_string(),
+    };
+    let width = rounds.iter().flatten().flatten().max().map_or(1, |max| max.to_string().len());
+    let separator = if width > 1 { " " } else { "" };
+    let mut out = String::new();
+    for (row, cells) in grid.iter().enumerate() {
+        let cells: Vec<String> = (0..cells.len()).map(|col| format!("{:>width$}", label(row, col))).collect();
+        out.push_str(&cells.join(separator));
+        out.push('\n');
+    }
+    out
+}
+
+/// One frame per round: rolls removed in that round as `x`, earlier ones already gone.
+fn animate_rounds(vis: &mut Visualizer, grid: &[Vec<char>], rounds: &[Vec<Option<usize>>]) -> std::io::Result<()> {
+    let last = rounds.iter().flatten().flatten().max().copied().unwrap_or(0);
+    for round in 1..=last {
+        let frame: Vec<Vec<char>> = grid
+            .iter()
+            .zip(rounds)
+            .map(|(cells, removed)| {
+                cells
+                    .iter()
+                    .zip(removed)
+                    .map(|(&cell, removed)| match removed {
+                        Some(r) if *r < round => '.',
+                        Some(r) if *r == round => 'x',
+                        _ => cell,
+                    })
+                    .collect()
+            })
+            .collect();
+        let removed = rounds.iter().flatten().filter(|r| **r == Some(round)).count();
+        vis.show(&format!("Day 4 - round {}: {} rolls removed", round, removed), &frame)?;
+    }
+    Ok(())
+}
+
+/// Removal order as a heat map: early rounds dark, late rounds bright, remaining rolls black.
+fn heat_map(grid: &[Vec<char>], rounds: &[Vec<Option<usize>>]) -> Canvas {
+    let last = rounds.iter().flatten().flatten().max().copied().unwrap_or(1).max(2);
+    let colours: Vec<Vec<Option<Rgb>>> = grid
+        .iter()
+        .zip(rounds)
+        .map(|(cells, removed)| {
+            cells
+                .iter()
+                .zip(removed)
+                .map(|(&cell, removed)| match removed {
+                    Some(round) => Some(Rgb::heat((round - 1) as f64 / (last - 1) as f64)),
+                    None if cell == '@' => Some(Rgb::BLACK),
+                    None => None,
+                })
+                .collect()
+        })
+        .collect();
+    let mut canvas = Canvas::new(grid[0].len() as f64, grid.len() as f64);
+    canvas.cells(&colours);
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimised fuzz inputs, also in fuzz/corpus/day04.
+    #[test]
+    fn ragged_grid() {
+        let input = "@@@\n@@\n";
+        assert!(part1(input.as_bytes()).is_err());
+        assert!(part2(input.as_bytes(), Output::default(), false).is_err());
+    }
+
+    #[test]
+    fn empty_grid() {
+        assert!(part1("".as_bytes()).is_err());
+        assert!(part2("".as_bytes(), Output::default(), false).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+// Day 5: fresh ingredient ID ranges.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::BufRead;
+use crate::input::SectionedInput;
+use crate::parse::*;
+use crate::ranges;
+use crate::validate::Violation;
+
+// `start-end` with `start <= end`. Bounds fit in a u64, so no count of IDs can overflow.
+fn range(input: Input) -> PResult<(u128, u128)> {
+    let bound = || map(uint::<u64>(), u128::from);
+    let ((pos, (start, end)), input) = spanned(pair(bound(), preceded(ch('-'), bound())))(input)?;
+    if end < start {
+        return Err(ParseError::at(pos, format!("range {}-{} ends before it starts", start, end)));
+    }
+    Ok(((start, end), input))
+}
+
+// Fresh ID ranges and the available ingredient IDs.
+type Inventory = (Vec<(u128, u128)>, Vec<u128>);
+
+const SECTIONS: [&str; 2] = ["ranges", "ids"];
+
+fn parse<R: BufRead>(reader: R) -> Result<Inventory, Box<dyn Error>> {
+    let input = SectionedInput::read(reader)?.with_names(&SECTIONS)?;
+    let ranges = input.named("ranges")?.parse(range)?;
+    let ids = input.named("ids")?.parse(uint())?;
+    Ok((ranges, ids))
+}
+
+/// A section of `start-end` ranges, a blank line, then a section of IDs.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let input = match SectionedInput::read(input.as_bytes()).map(|input| input.with_names(&SECTIONS)) {
+        Ok(Ok(input)) => input,
+        Ok(Err(err)) => return vec![Violation::input(err.to_string())],
+        Err(err) => return vec![Violation::input(err.to_string())],
+    };
+    let sections = input.sections();
+    let (ranges, ids) = (&sections[0], &sections[1]);
+    let mut violations = Vec::new();
+    for (i, line) in ranges.lines.iter().enumerate() {
+        violations.extend(parse_line(ranges.first_line + i, line, range).err().map(Violation::from));
+    }
+    for (i, line) in ids.lines.iter().enumerate() {
+        violations.extend(parse_line(ids.first_line + i, line, uint::<u128>()).err().map(Violation::from));
+    }
+    violations
+}
+
+/// Brute force for part 2: collects every fresh ID one by one.
+pub fn reference_part2(input: &str) -> Result<u128, Box<dyn Error>> {
+    let (ranges, _) = parse(input.as_bytes())?;
+    let mut fresh = HashSet::new();
+    for (s, e) in ranges {
+        if e >= s && e - s > 1_000_000 {
+            return Err(format!("range {}-{} is too large to enumerate", s, e).into());
+        }
+        fresh.extend(s..=e);
+    }
+    Ok(fresh.len() as u128)
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<u128, Box<dyn Error>> {
+    let (ranges, ids) = parse(reader)?;
+    Ok(ids.into_iter().filter(|&id| is_fresh(&ranges, id)).count() as u128)
+}
+
+pub fn part2<R: BufRead>(reader: R) -> Result<u128, Box<dyn Error>> {
+    let (ranges, _) = parse(reader)?;
+    Ok(ranges::covered(&ranges))
+}
+
+fn is_fresh(ranges: &[(u128, u128)], id: u128) -> bool {
+    for range in ranges {
+        let (s, e) = range;
+        if *s <= id && id <= *e {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A minimised fuzz input, also in fuzz/corpus/day05.
+    #[test]
+    fn reversed_range() {
+        let input = "5-3\n\n4\n";
+        assert!(part1(input.as_bytes()).is_err());
+        assert!(part2(input.as_bytes()).is_err());
+    }
+}
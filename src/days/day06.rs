@@ -0,0 +1,59 @@
+// Day 6: worksheets of problems written in columns.
+
+use std::error::Error;
+use std::io::BufRead;
+use crate::{expr, worksheet};
+use crate::answer::Answer;
+use crate::validate::{self, Violation};
+
+/// Rows of digits above one row of operators, each a known operator.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let lines: Vec<(usize, &str)> = validate::lines(input).collect();
+    let Some((&(op_line, ops), numbers)) = lines.split_last() else {
+        return vec![Violation::input("the worksheet is empty")];
+    };
+    let mut violations = Vec::new();
+    for &(line_no, line) in numbers {
+        for (j, c) in line.chars().enumerate() {
+            if !c.is_ascii_digit() && c != ' ' {
+                violations.push(Violation::at(line_no, j + 1, format!("unexpected {:?} among the numbers", c)));
+            }
+        }
+    }
+    let mut column = 0;
+    for op in ops.split(' ') {
+        if !op.is_empty() {
+            if let Err(err) = expr::operator(op) {
+                violations.push(Violation::at(op_line, column + 1, err.to_string()));
+            }
+        }
+        column += op.chars().count() + 1;
+    }
+    violations
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<Answer, Box<dyn Error>> {
+    let mut ans = Answer::zero();
+    for block in worksheet::read_blocks(reader)? {
+        let (numbers, op) = block.split_last_row();
+        let numbers = numbers.rows()
+            .filter(|row| !row.trim().is_empty())
+            .map(|row| row.trim().parse::<i128>())
+            .collect::<Result<Vec<_>, _>>()?;
+        ans += expr::evaluate_row_answer(&op, &numbers)?;
+    }
+    Ok(ans)
+}
+
+pub fn part2<R: BufRead>(reader: R) -> Result<Answer, Box<dyn Error>> {
+    let mut ans = Answer::zero();
+    for block in worksheet::read_blocks(reader)? {
+        let (numbers, op) = block.split_last_row();
+        let numbers = numbers.columns_rtl()
+            .filter(|col| !col.trim().is_empty())
+            .map(|col| col.trim().parse::<i128>())
+            .collect::<Result<Vec<_>, _>>()?;
+        ans += expr::evaluate_row_answer(&op, &numbers)?;
+    }
+    Ok(ans)
+}
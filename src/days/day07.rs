@@ -0,0 +1,117 @@
+// Day 7: tachyon beams split on their way down the manifold.
+
+use std::error::Error;
+use std::io::BufRead;
+use crate::answer::Answer;
+use crate::beam::{stream_downward, Dir, Grid, Simulation};
+use crate::beam::Cell;
+use crate::canvas::{self, Canvas, Rgb, Shape};
+use crate::validate::{self, Violation};
+use crate::visualize::{Colour, Output, Visualizer};
+
+fn parse<R: BufRead>(reader: R) -> Result<Grid, Box<dyn Error>> {
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+    Ok(Grid::parse(&lines)?)
+}
+
+/// A rectangle of known cells with exactly one `S`, in the first row.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut first: Option<(usize, usize)> = None;
+    let mut start = None;
+    for (line_no, line) in validate::lines(input) {
+        let line = line.trim();
+        let (first_line, width) = *first.get_or_insert((line_no, line.chars().count()));
+        for (j, c) in line.chars().enumerate() {
+            match Cell::from_char(c) {
+                None => violations.push(Violation::at(line_no, j + 1, format!("unknown cell {:?}", c))),
+                Some(Cell::Source) if line_no != first_line => {
+                    violations.push(Violation::at(line_no, j + 1, "'S' below the first row"))
+                }
+                Some(Cell::Source) if start.is_some() => {
+                    violations.push(Violation::at(line_no, j + 1, "more than one 'S' in the first row"))
+                }
+                Some(Cell::Source) => start = Some(j),
+                Some(_) => {}
+            }
+        }
+        if line.chars().count() != width {
+            violations.push(Violation::line(line_no, format!("expected {} cells, found {}", width, line.chars().count())));
+        }
+    }
+    match first {
+        None => violations.push(Violation::input("the grid is empty")),
+        Some((line, _)) if start.is_none() => violations.insert(0, Violation::line(line, "no 'S' in the first row")),
+        _ => {}
+    }
+    violations
+}
+
+pub fn part1<R: BufRead>(reader: R) -> Result<usize, Box<dyn Error>> {
+    Ok(stream_downward(reader)?.activated)
+}
+
+/// With `show_timelines`, also prints how many timelines pass through every cell.
+pub fn part2<R: BufRead>(reader: R, out: Output, show_timelines: bool) -> Result<Answer, Box<dyn Error>> {
+    if !show_timelines && out.visualizer.is_none() && out.export.is_none() {
+        return Ok(stream_downward(reader)?.timelines);
+    }
+    // The table, animation and picture need the whole grid, so this path does not stream.
+    let grid = parse(reader)?;
+    let sim = grid.simulate();
+    if show_timelines {
+        if let Some(table) = sim.render_timelines(&grid) {
+            print!("{}", table);
+        }
+    }
+    if let Some(vis) = out.visualizer {
+        let mut vis = vis.with_palette(&[('|', Colour::Cyan), ('^', Colour::Magenta), ('S', Colour::Yellow), ('.', Colour::Grey)]);
+        animate_beams(&mut vis, &grid, &sim)?;
+    }
+    canvas::export(out.export.as_deref(), || picture(&grid, &sim))?;
+    // Only beams reaching the bottom are timelines; ones stepping off the sides are lost.
+    sim.timelines_through(Some(Dir::Down)).ok_or_else(|| "beams loop forever".into())
+}
+
+/// One frame per row, drawing the beams as `|` down to that row.
+fn animate_beams(vis: &mut Visualizer, grid: &Grid, sim: &Simulation) -> std::io::Result<()> {
+    let mut frame: Vec<Vec<char>> = (0..grid.height())
+        .map(|r| (0..grid.width()).map(|c| grid.get(r, c).to_char()).collect())
+        .collect();
+    for r in 0..grid.height() {
+        for (cell, &energized) in frame[r].iter_mut().zip(&sim.energized[r]) {
+            if energized && *cell == '.' {
+                *cell = '|';
+            }
+        }
+        vis.show(&format!("Day 7 - row {}", r + 1), &frame)?;
+    }
+    Ok(())
+}
+
+/// Timeline counts as a heat map on a log scale, with splitters in black and, in SVG, the counts written in.
+fn picture(grid: &Grid, sim: &Simulation) -> Canvas {
+    let mut canvas = Canvas::new(grid.width() as f64, grid.height() as f64).with_scale(16);
+    let Some(counts) = sim.cell_timelines.as_ref() else { return canvas };
+    let max = counts.iter().flatten().map(|c| c.to_f64()).fold(1.0, f64::max);
+    let colours: Vec<Vec<Option<Rgb>>> = (0..grid.height())
+        .map(|r| {
+            (0..grid.width())
+                .map(|c| match grid.get(r, c) {
+                    Cell::Empty if counts[r][c] != Answer::zero() => Some(Rgb::heat(counts[r][c].to_f64().ln_1p() / max.ln_1p())),
+                    Cell::Empty => None,
+                    _ => Some(Rgb::BLACK),
+                })
+                .collect()
+        })
+        .collect();
+    canvas.cells(&colours);
+    for (r, row) in counts.iter().enumerate() {
+        for (c, count) in row.iter().enumerate() {
+            if grid.get(r, c) == Cell::Empty && *count != Answer::zero() {
+                canvas.add(Shape::Text { x: c as f64 + 0.5, y: r as f64 + 0.5, text: count.to_string(), colour: Rgb::WHITE });
+            }
+        }
+    }
+    canvas
+}
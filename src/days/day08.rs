@@ -0,0 +1,124 @@
+// Day 8: junction boxes joined into circuits, closest pairs first.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::BufRead;
+use std::path::Path;
+use crate::dot::{self, Dot};
+use crate::parse::*;
+use crate::union_find::UnionFind;
+use crate::validate::{self, Violation};
+
+// `x,y,z`. Coordinates fit in an i32, so squared distances cannot overflow.
+fn position(input: Input) -> PResult<(i128, i128, i128)> {
+    let int = || map(int::<i32>(), i128::from);
+    let coord = || preceded(ch(','), int());
+    map(pair(int(), pair(coord(), coord())), |(x, (y, z))| (x, y, z))(input)
+}
+
+/// One `x,y,z` junction box per line, no two in the same place.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut seen: HashMap<(i128, i128, i128), usize> = HashMap::new();
+    for (line_no, line) in validate::lines(input) {
+        match parse_line(line_no, line, position) {
+            Ok(p) => match seen.get(&p) {
+                Some(first) => violations.push(Violation::line(line_no, format!("junction box already on line {}", first))),
+                None => {
+                    seen.insert(p, line_no);
+                }
+            },
+            Err(err) => violations.push(err.into()),
+        }
+    }
+    violations
+}
+
+pub fn part1<R: BufRead>(reader: R, r: i32, dot_path: Option<&Path>) -> Result<i128, Box<dyn Error>> {
+    let positions: Vec<(i128, i128, i128)> = parse_lines(reader, position)?;
+    let mut positions_by_dist: Vec<(i128, (usize, usize))> = Vec::new();
+    let n = positions.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            let d = dist(positions[i], positions[j]);
+            positions_by_dist.push((d, (i, j)));
+        }
+    }
+    positions_by_dist.sort();
+
+    let mut circuits = UnionFind::new(n);
+    let connections: Vec<(usize, usize)> = positions_by_dist.iter().take(r as usize).map(|p| p.1).collect();
+    for &(a, b) in &connections {
+        circuits.union(a, b);
+    }
+    let ids: Vec<usize> = (0..n).map(|i| circuits.find(i)).collect();
+    dot::export(dot_path, || circuits_dot(&positions, &ids, &connections))?;
+    Ok(circuits.sizes().into_iter().take(3).map(|size| size as i128).product())
+}
+
+pub fn part2<R: BufRead>(reader: R) -> Result<i128, Box<dyn Error>> {
+    let positions: Vec<(i128, i128, i128)> = parse_lines(reader, position)?;
+    let mut positions_by_dist: Vec<(i128, (usize, usize))> = Vec::new();
+    let n = positions.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            let d = dist(positions[i], positions[j]);
+            positions_by_dist.push((d, (i, j)));
+        }
+    }
+    positions_by_dist.sort();
+
+    let mut circuits = UnionFind::new(n);
+    for p in positions_by_dist {
+        if circuits.union(p.1.0, p.1.1) && circuits.components() == 1 {
+            return Ok(positions[p.1.0].0 * positions[p.1.1].0);
+        }
+    }
+    Ok(0)
+}
+
+fn dist(a: (i128, i128, i128), b: (i128, i128, i128)) -> i128 {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    let dz = a.2 - b.2;
+    dx * dx + dy * dy + dz * dz
+}
+
+/// Junction boxes grouped by circuit, joined by the connections in the order they were made.
+fn circuits_dot(positions: &[(i128, i128, i128)], ids: &[usize], connections: &[(usize, usize)]) -> Dot {
+    let name = |i: usize| format!("{},{},{}", positions[i].0, positions[i].1, positions[i].2);
+    let mut circuits: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &id) in ids.iter().enumerate() {
+        circuits.entry(id).or_default().push(i);
+    }
+    let mut circuits: Vec<Vec<usize>> = circuits.into_values().collect();
+    circuits.sort_by_key(|boxes| (std::cmp::Reverse(boxes.len()), boxes[0]));
+
+    let mut dot = Dot::graph("circuits");
+    for (n, boxes) in circuits.iter().enumerate() {
+        let names: Vec<String> = boxes.iter().map(|&i| name(i)).collect();
+        if boxes.len() == 1 {
+            dot.node(&names[0], &[]);
+        } else {
+            let nodes: Vec<(&str, &[(&str, &str)])> = names.iter().map(|n| (n.as_str(), &[][..])).collect();
+            dot.cluster(&format!("circuit {} ({} boxes)", n + 1, boxes.len()), &nodes);
+        }
+    }
+    for (order, &(a, b)) in connections.iter().enumerate() {
+        dot.edge(&name(a), &name(b), &[("label", &format!("#{}", order + 1))]);
+    }
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A minimised fuzz input, also in fuzz/corpus/day08.
+    #[test]
+    fn coordinates_overflowing_the_distance() {
+        let input = "99999999999999999999,0,0\n-99999999999999999999,0,0\n";
+        assert!(part1(input.as_bytes(), 1, None).is_err());
+        assert!(part2(input.as_bytes()).is_err());
+    }
+}
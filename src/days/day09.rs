@@ -0,0 +1,112 @@
+// Day 9: the largest rectangle between two red tiles.
+
+use std::error::Error;
+use std::io::BufRead;
+use itertools::Itertools;
+use crate::parse::*;
+use crate::canvas;
+use crate::polygon::{self, Grid, Rect, Tile};
+use crate::validate::{self, Violation};
+use crate::visualize::{Colour, Output};
+
+// `col,row`. Coordinates fit in an i32, so no area can overflow.
+fn tile(input: Input) -> PResult<Tile> {
+    let int = || map(int::<i32>(), i128::from);
+    map(pair(int(), preceded(ch(','), int())), |(col, row)| Tile::new(row, col))(input)
+}
+
+fn parse<R: BufRead>(reader: R) -> Result<Vec<Tile>, Box<dyn Error>> {
+    parse_lines(reader, tile)
+}
+
+/// One `col,row` red tile per line, going round a simple polygon: each tile shares a row or a
+/// column with the next, the path turns at every tile and never crosses itself.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut tiles = Vec::new();
+    let mut lines = Vec::new();
+    for (line_no, line) in validate::lines(input) {
+        match parse_line(line_no, line, tile) {
+            Ok(t) => {
+                tiles.push(t);
+                lines.push(line_no);
+            }
+            Err(err) => violations.push(err.into()),
+        }
+    }
+    if violations.is_empty() {
+        violations.extend(polygon::problems(&tiles).into_iter().map(|(i, problem)| match lines.get(i) {
+            Some(&line) => Violation::line(line, problem),
+            None => Violation::input(problem),
+        }));
+    }
+    violations
+}
+
+/// Brute force for part 2: tests every tile of every candidate rectangle against the polygon.
+pub fn reference_part2(input: &str) -> Result<i128, Box<dyn Error>> {
+    let tiles = parse(input.as_bytes())?;
+    polygon::check(&tiles)?;
+    let edges: Vec<(Tile, Tile)> = tiles.iter().copied().circular_tuple_windows().collect();
+    let between = |v: i128, a: i128, b: i128| a.min(b) <= v && v <= a.max(b);
+    let red_or_green = |row: i128, col: i128| {
+        let on_edge = edges.iter().any(|(a, b)| {
+            (a.row == b.row && a.row == row && between(col, a.col, b.col))
+                || (a.col == b.col && a.col == col && between(row, a.row, b.row))
+        });
+        // Count the vertical edges to the right, each covering the rows [min, max).
+        let crossings = edges
+            .iter()
+            .filter(|(a, b)| a.col == b.col && a.col > col && a.row.min(b.row) <= row && row < a.row.max(b.row))
+            .count();
+        on_edge || crossings % 2 == 1
+    };
+    let mut best = 0;
+    for (i, t1) in tiles.iter().enumerate() {
+        for t2 in &tiles[i + 1..] {
+            let rect = Rect::new(t1.row, t1.col, t2.row, t2.col);
+            if t1.area(t2) > best
+                && (rect.r1..=rect.r2).cartesian_product(rect.c1..=rect.c2).all(|(r, c)| red_or_green(r, c))
+            {
+                best = t1.area(t2);
+            }
+        }
+    }
+    Ok(best)
+}
+
+pub fn part1<R: BufRead>(reader: R, _r: i32) -> Result<i128, Box<dyn Error>> {
+    Ok(polygon::largest(&parse(reader)?))
+}
+
+pub fn part2<R: BufRead>(reader: R, out: Output) -> Result<i128, Box<dyn Error>> {
+    let tiles = parse(reader)?;
+    polygon::check(&tiles)?;
+    let grid = Grid::new(&tiles);
+    let (ans, best) = grid.largest_inside(&tiles).ok_or("no rectangle fits inside the red tiles")?;
+    if let Some(vis) = out.visualizer {
+        let mut vis = vis.with_palette(&[('o', Colour::Grey), ('i', Colour::Green), ('b', Colour::Yellow), ('#', Colour::Red)]);
+        vis.show("Day 9 - compressed tiles: (o)utside, (i)nside, (b)oundary", &grid.tiles)?;
+        vis.show(&format!("Day 9 - largest rectangle: {}", ans), &grid.highlight(best))?;
+    }
+    canvas::export(out.export.as_deref(), || grid.picture(best))?;
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimised fuzz inputs, also in fuzz/corpus/day09.
+    #[test]
+    fn coordinates_overflowing_the_area() {
+        let input = "0,0\n99999999999999999999,99999999999999999999\n";
+        assert!(part1(input.as_bytes(), 0).is_err());
+        assert!(part2(input.as_bytes(), Output::default()).is_err());
+    }
+
+    #[test]
+    fn too_few_red_tiles() {
+        assert!(part2("1,1\n3,1\n".as_bytes(), Output::default()).is_err());
+    }
+}
@@ -0,0 +1,437 @@
+// Day 10: button presses for each machine's lights and joltage.
+
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use indicatif::{ProgressBar, ProgressStyle};
+use crate::{gf2, ilp, lp};
+use crate::parse::*;
+use crate::runner::{self, Job};
+use crate::validate::{self, Violation};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    Model,
+    Exact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Lights,
+    Joltage,
+}
+
+// How many times each button of one machine is pressed. Run with `--plans` to print them.
+#[derive(Debug, Clone)]
+struct PressPlan {
+    line: usize,
+    target: Target,
+    buttons: Vec<Vec<usize>>,
+    presses: Vec<u64>,
+    total: u64,
+}
+
+impl PressPlan {
+    fn new(line: usize, target: Target, buttons: &[Vec<usize>], presses: Vec<u64>) -> Result<Self, String> {
+        let total = presses.iter().try_fold(0u64, |sum, &n| sum.checked_add(n))
+            .ok_or(format!("line {}: too many presses to count", line))?;
+        Ok(Self { line, target, buttons: buttons.to_vec(), presses, total })
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+
+    // `None` if a counter overflows.
+    fn simulate(&self, counters: usize) -> Option<Vec<u64>> {
+        let mut state = vec![0u64; counters];
+        for (button, &n) in self.buttons.iter().zip(self.presses.iter()) {
+            for &index in button.iter() {
+                state[index] = state[index].checked_add(n)?;
+            }
+        }
+        if self.target == Target::Lights {
+            state.iter_mut().for_each(|v| *v %= 2);
+        }
+        Some(state)
+    }
+
+    fn verify(&self, expected: &[usize]) -> Result<(), String> {
+        let state = self.simulate(expected.len())
+            .ok_or(format!("line {}: {:?} plan overflows a counter", self.line, self.target))?;
+        if state.iter().zip(expected.iter()).all(|(&s, &e)| s == e as u64) {
+            Ok(())
+        } else {
+            Err(format!("line {}: {:?} plan produces {:?}, expected {:?}", self.line, self.target, state, expected))
+        }
+    }
+}
+
+impl fmt::Display for PressPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "line {} ({:?}): {} presses", self.line, self.target, self.total())?;
+        let wirings: Vec<String> = self.buttons.iter()
+            .map(|button| format!("({})", button.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(",")))
+            .collect();
+        let width = wirings.iter().map(|w| w.len()).max().unwrap_or(0).max("wiring".len());
+        writeln!(f, "  button | {:<width$} | presses", "wiring", width = width)?;
+        for (i, (wiring, &n)) in wirings.iter().zip(self.presses.iter()).enumerate() {
+            if n > 0 {
+                writeln!(f, "  {:>6} | {:<width$} | {:>7}", i, wiring, n, width = width)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Machine {
+    line: usize,
+    lights: Vec<usize>,
+    buttons: Vec<Vec<usize>>,
+    joltage: Vec<usize>,
+}
+
+fn lights(input: Input) -> PResult<Vec<usize>> {
+    delimited(ch('['), many1(map(one_of(".#"), |c| if c == '#' { 1 } else { 0 })), ch(']'))(input)
+}
+
+fn list<'a>(open: char, close: char) -> impl Parser<'a, Vec<(Position, usize)>> {
+    delimited(ch(open), sep_by1(spanned(uint()), ch(',')), ch(close))
+}
+
+// [lights] (button)... {joltage}, with button indices and the joltage count checked
+// against the number of lights, and each light listed at most once per button.
+fn machine(input: Input) -> PResult<Machine> {
+    let line = input.position().line;
+    let (lights, input) = token(lights)(input)?;
+    let (buttons, input) = many(token(list('(', ')')))(input)?;
+    let ((joltage_start, joltage), input) = spanned(list('{', '}'))(input)?;
+
+    for button in &buttons {
+        for (j, &(pos, index)) in button.iter().enumerate() {
+            if index >= lights.len() {
+                return Err(ParseError::at(pos, format!(
+                    "button index {} is out of range for {} lights", index, lights.len())));
+            }
+            if button[..j].iter().any(|&(_, earlier)| earlier == index) {
+                return Err(ParseError::at(pos, format!("button lists light {} twice", index)));
+            }
+        }
+    }
+    if joltage.len() != lights.len() {
+        return Err(ParseError::at(joltage_start, format!(
+            "expected {} joltage values, found {}", lights.len(), joltage.len())));
+    }
+
+    let values = |list: Vec<(Position, usize)>| list.into_iter().map(|(_, v)| v).collect();
+    let buttons = buttons.into_iter().map(values).collect();
+    Ok((Machine { line, lights, buttons, joltage: values(joltage) }, input))
+}
+
+/// One machine per line, with button indices and joltage values matching its lights, and no
+/// button wired to the same light twice.
+pub fn validate(input: &str) -> Vec<Violation> {
+    validate::lines(input)
+        .filter_map(|(line_no, line)| parse_line(line_no, line, machine).err())
+        .map(Violation::from)
+        .collect()
+}
+
+fn parse<R: BufRead>(reader: R) -> Result<Vec<Machine>, Box<dyn Error>> {
+    parse_lines(reader, machine)
+}
+
+// Tries each count for `buttons[i..]` in turn, largest first, keeping the fewest presses that
+// bring every counter in `remaining` to zero.
+fn fewest_presses(remaining: &mut [usize], buttons: &[Vec<usize>], i: usize, pressed: u64, best: &mut Option<u64>) {
+    if best.is_some_and(|b| pressed >= b) {
+        return;
+    }
+    if i == buttons.len() {
+        if remaining.iter().all(|&r| r == 0) {
+            *best = Some(pressed);
+        }
+        return;
+    }
+    let bound = buttons[i].iter().map(|&c| remaining[c]).min().unwrap_or(0);
+    for n in (0..=bound).rev() {
+        buttons[i].iter().for_each(|&c| remaining[c] -= n);
+        fewest_presses(remaining, buttons, i + 1, pressed + n as u64, best);
+        buttons[i].iter().for_each(|&c| remaining[c] += n);
+    }
+}
+
+/// Brute force for both parts: every subset of buttons for the lights, and a search over every
+/// press count for the joltage.
+pub fn reference(input: &str) -> Result<(u128, u64, u64), Box<dyn Error>> {
+    let (mut part1, mut part2) = (0, 0);
+    for machine in parse(input.as_bytes())? {
+        let lights = (0..1u32 << machine.buttons.len())
+            .filter(|mask| {
+                let mut on = vec![0; machine.lights.len()];
+                for (b, button) in machine.buttons.iter().enumerate() {
+                    if mask & (1 << b) != 0 {
+                        button.iter().for_each(|&i| on[i] ^= 1);
+                    }
+                }
+                on == machine.lights
+            })
+            .map(|mask| mask.count_ones())
+            .min()
+            .ok_or(format!("line {}: cannot reach the light diagram", machine.line))?;
+        part1 += lights as u128;
+
+        let mut best = None;
+        fewest_presses(&mut machine.joltage.clone(), &machine.buttons, 0, 0, &mut best);
+        part2 += best.ok_or(format!("line {}: cannot reach the joltage", machine.line))?;
+    }
+    Ok((part1, part2, part2))
+}
+
+pub fn part1<R: BufRead>(reader: R, _r: i32, plans: bool) -> Result<u128, Box<dyn Error>> {
+    let mut ans = 0;
+    for machine in parse(reader)? {
+        let presses = find(&machine.lights, &machine.buttons)
+            .map_err(|e| format!("line {}: {}", machine.line, e))?
+            .ok_or(format!("line {}: cannot reach the light diagram", machine.line))?;
+        let plan = PressPlan::new(machine.line, Target::Lights, &machine.buttons, presses)?;
+        plan.verify(&machine.lights)?;
+        if plans {
+            println!("{}", plan);
+        }
+        ans += plan.total() as u128;
+    }
+    Ok(ans)
+}
+
+/// How part 2 solves its machines: on `threads` workers, with a progress bar and then the
+/// slowest machines if `progress` is set, giving up on any machine that takes longer than
+/// `time_limit`, and printing each plan if `plans` is set. If a machine fails, its model is
+/// written to `dump` as LP or MPS. The default solves them one at a time, quietly and without
+/// a limit.
+#[derive(Debug, Clone)]
+pub struct Solving {
+    pub threads: usize,
+    pub progress: bool,
+    pub plans: bool,
+    /// Only the exact backend can be stopped, so the model backend refuses a limit.
+    pub time_limit: Option<Duration>,
+    pub dump: Option<PathBuf>,
+}
+
+impl Default for Solving {
+    fn default() -> Self {
+        Self { threads: 1, progress: false, plans: false, time_limit: None, dump: None }
+    }
+}
+
+impl Solving {
+    /// One worker per CPU unless `--threads=N` says otherwise, with `--progress`, `--plans`,
+    /// `--time-limit=MS` and `--dump=PATH` from the command line. Fails on a value it cannot use.
+    pub fn from_args() -> Result<Self, String> {
+        let mut solving = Self { threads: runner::threads(), ..Self::default() };
+        for arg in std::env::args() {
+            if arg == "--progress" {
+                solving.progress = true;
+            } else if arg == "--plans" {
+                solving.plans = true;
+            } else if let Some(n) = arg.strip_prefix("--threads=") {
+                solving.threads = n.parse().map_err(|e| format!("{}: {}", arg, e))?;
+            } else if let Some(ms) = arg.strip_prefix("--time-limit=") {
+                let ms = ms.parse().map_err(|e| format!("{}: {}", arg, e))?;
+                solving.time_limit = Some(Duration::from_millis(ms));
+            } else if let Some(path) = arg.strip_prefix("--dump=") {
+                if !path.ends_with(".lp") && !path.ends_with(".mps") {
+                    return Err(format!("{}: expected a .lp or .mps file", arg));
+                }
+                solving.dump = Some(PathBuf::from(path));
+            }
+        }
+        Ok(solving)
+    }
+}
+
+// Shown with `--progress` once every machine is solved.
+const SLOWEST: usize = 5;
+
+// A machine's plan, or `None` if it ran out of time, and how long it took.
+type Solved = (Result<Option<PressPlan>, String>, Duration);
+
+pub fn part2<R: BufRead>(reader: R, backend: Backend, solving: Solving) -> Result<u64, Box<dyn Error>> {
+    if let (Backend::Model, Some(_)) = (backend, solving.time_limit) {
+        return Err("a time limit needs the exact backend; the model solvers cannot be stopped".into());
+    }
+    let machines = parse(reader)?;
+    let bar = match solving.progress {
+        true => ProgressBar::new(machines.len() as u64),
+        false => ProgressBar::hidden(),
+    };
+    bar.set_style(ProgressStyle::with_template("{bar:40} {pos}/{len} machines, {elapsed}")?);
+    bar.enable_steady_tick(Duration::from_millis(100));
+
+    // Machines are independent, so each is a job of its own.
+    let jobs: Vec<Job<Solved>> = machines
+        .iter()
+        .map(|machine| -> Job<_> {
+            Box::new(move || {
+                let start = Instant::now();
+                let plan = joltage_plan(machine, backend, solving.time_limit.map(|limit| start + limit));
+                (plan, start.elapsed())
+            })
+        })
+        .collect();
+    let results = runner::run_all(jobs, solving.threads, |_, _| bar.inc(1));
+    bar.finish_and_clear();
+
+    let mut ans = 0;
+    let mut timings = Vec::new();
+    let mut out_of_time = Vec::new();
+    for (machine, outcome) in machines.iter().zip(results) {
+        let (plan, elapsed) = outcome.map_err(|message| format!("line {}: panicked: {}", machine.line, message))?;
+        timings.push((machine.line, elapsed));
+        let plan = match (plan, &solving.dump) {
+            (Err(e), Some(path)) => {
+                model(&machine.joltage, &machine.buttons, machine.line).0.save(path)?;
+                return Err(format!("{} (model written to {})", e, path.display()).into());
+            }
+            (plan, _) => plan?,
+        };
+        match plan {
+            Some(plan) => {
+                if solving.plans {
+                    println!("{}", plan);
+                }
+                ans = plan.total().checked_add(ans).ok_or("the total number of presses overflows")?;
+            }
+            None => out_of_time.push(machine.line.to_string()),
+        }
+    }
+    if solving.progress {
+        timings.sort_by_key(|&(_, elapsed)| Reverse(elapsed));
+        println!("Slowest machines:");
+        for (line, elapsed) in timings.into_iter().take(SLOWEST) {
+            println!("  line {}: {:.2?}", line, elapsed);
+        }
+    }
+    if let (false, Some(limit)) = (out_of_time.is_empty(), solving.time_limit) {
+        let lines = if out_of_time.len() == 1 { "line" } else { "lines" };
+        return Err(format!("not solved within {:?}: {} {}", limit, lines, out_of_time.join(", ")).into());
+    }
+    Ok(ans)
+}
+
+// `None` if the machine is not solved by `deadline`.
+fn joltage_plan(machine: &Machine, backend: Backend, deadline: Option<Instant>) -> Result<Option<PressPlan>, String> {
+    let presses = match backend {
+        // `part2` only sets a deadline for the exact backend.
+        Backend::Model => ilp(machine.line, &machine.joltage, &machine.buttons)?,
+        Backend::Exact => match exact(&machine.joltage, &machine.buttons, deadline) {
+            Ok(presses) => presses.ok_or(format!("line {}: cannot reach the joltage", machine.line))?,
+            Err(ilp::IlpError::TimedOut) => return Ok(None),
+            Err(e) => return Err(format!("line {}: {}", machine.line, e)),
+        },
+    };
+    let plan = PressPlan::new(machine.line, Target::Joltage, &machine.buttons, presses)?;
+    plan.verify(&machine.joltage)?;
+    Ok(Some(plan))
+}
+
+fn find(target: &[usize], buttons: &[Vec<usize>]) -> Result<Option<Vec<u64>>, gf2::TooManyFree> {
+    // Pressing a button twice cancels out, so each button is pressed 0 or 1 times
+    // and every light gives one equation over GF(2).
+    let mut system = gf2::Gf2System::new(buttons.len());
+    for (light, &on) in target.iter().enumerate() {
+        let pressed_by: Vec<usize> = buttons.iter()
+            .enumerate()
+            .filter(|(_, button)| button.contains(&light))
+            .map(|(button_idx, _)| button_idx)
+            .collect();
+        system.add_equation(gf2::BitVec::from_indices(buttons.len(), &pressed_by), on == 1);
+    }
+    Ok(system.solve_min_weight()?
+        .map(|pressed| (0..buttons.len()).map(|i| if pressed.get(i) { 1 } else { 0 }).collect()))
+}
+
+// Press counts `x` minimising their sum, with each counter's buttons adding up to its joltage.
+fn model(target: &[usize], buttons: &[Vec<usize>], line: usize) -> (lp::Model, Vec<lp::Var>) {
+    let mut model = lp::Model::new(&format!("day10_line{}", line));
+    let x: Vec<lp::Var> = buttons.iter()
+        .enumerate()
+        .map(|(button_idx, button)| {
+            let bound = button.iter().map(|&counter_idx| target[counter_idx]).min().unwrap_or(0);
+            model.add_integer(&format!("b{}", button_idx), 0.0, Some(bound as f64))
+        })
+        .collect();
+
+    model.minimise(x.iter().map(|&v| (v, 1.0)).collect());
+
+    for (counter_idx, &joltage) in target.iter().enumerate() {
+        let terms = buttons.iter()
+            .enumerate()
+            .filter(|(_, button)| button.contains(&counter_idx))
+            .map(|(button_idx, _)| (x[button_idx], 1.0))
+            .collect();
+        model.add_constraint(&format!("c{}", counter_idx), terms, lp::Sense::Eq, joltage as f64);
+    }
+    (model, x)
+}
+
+fn ilp(line: usize, target: &[usize], buttons: &[Vec<usize>]) -> Result<Vec<u64>, String> {
+    let (model, x) = model(target, buttons, line);
+    let solution = model.solve().map_err(|e| format!("line {}: {}", line, e))?;
+
+    Ok(x.iter()
+        .map(|&var| solution.value(var).round() as u64)
+        .collect())
+}
+
+fn exact(target: &[usize], buttons: &[Vec<usize>], deadline: Option<Instant>) -> Result<Option<Vec<u64>>, ilp::IlpError> {
+    let a: Vec<Vec<u64>> = (0..target.len())
+        .map(|counter_idx| buttons.iter()
+            .map(|button| if button.contains(&counter_idx) { 1 } else { 0 })
+            .collect())
+        .collect();
+    let b: Vec<u64> = target.iter().map(|&v| v as u64).collect();
+    match deadline {
+        Some(deadline) => ilp::minimize_sum_until(&a, &b, deadline),
+        None => ilp::minimize_sum(&a, &b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn button_listing_a_light_twice() {
+        let err = parse_line(1, "[##] (0) (1,0,1) {1,1}", machine).unwrap_err();
+        assert_eq!((15, "button lists light 1 twice"), (err.column, err.message.as_str()));
+    }
+
+    proptest! {
+        // Buttons pressed at random give lights and joltages whose plans must pass `verify`.
+        #[test]
+        fn plans_reproduce_targets(
+            buttons in prop::collection::vec(prop::collection::btree_set(0usize..5, 1..4), 1..6),
+            presses in prop::collection::vec(0usize..4, 6),
+        ) {
+            let buttons: Vec<Vec<usize>> = buttons.into_iter().map(|b| b.into_iter().collect()).collect();
+            let mut joltage = vec![0; 5];
+            for (button, &n) in buttons.iter().zip(&presses) {
+                button.iter().for_each(|&i| joltage[i] += n);
+            }
+            let lights: Vec<usize> = joltage.iter().map(|j| j % 2).collect();
+            let plan = PressPlan::new(1, Target::Lights, &buttons, find(&lights, &buttons).unwrap().unwrap()).unwrap();
+            prop_assert_eq!(Ok(()), plan.verify(&lights));
+            let machine = Machine { line: 1, lights, buttons, joltage };
+            for backend in [Backend::Model, Backend::Exact] {
+                prop_assert!(matches!(joltage_plan(&machine, backend, None), Ok(Some(_))));
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+// Day 11: paths through the reactor's devices.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::BufRead;
+use std::path::Path;
+use crate::answer::Answer;
+use crate::dot::{self, Dot};
+use crate::parse::*;
+use crate::validate::{self, Violation};
+
+struct Graph {
+    g: HashMap<u32, Vec<u32>>,
+    m: HashMap<String, u32>,
+    id: u32,
+}
+
+impl Graph {
+    fn new() -> Graph {
+        Self {
+            g: HashMap::new(),
+            m: HashMap::new(),
+            id: 0,
+        }
+    }
+
+    fn insert(&mut self, u: String, v: String) {
+        let u_id = self.get_id(u);
+        let v_id = self.get_id(v);
+        self.g.entry(u_id).or_default().push(v_id);
+    }
+
+    fn get_id(&mut self, u: String) -> u32 {
+        if self.m.contains_key(&u) {
+            self.m[&u]
+        } else {
+            let new_id = self.id;
+            self.m.insert(u, new_id);
+            self.id += 1;
+            new_id
+        }
+    }
+    /// One cycle of devices for each loop, in the order they feed each other. Peels off devices
+    /// with no remaining inputs; every device left has an input that is also left, so walking back
+    /// through those must come round to a cycle, which is peeled off in turn.
+    fn cycles(&self) -> Vec<Vec<&str>> {
+        let mut names = vec![""; self.id as usize];
+        self.m.iter().for_each(|(name, &id)| names[id as usize] = name.as_str());
+        let mut inputs = vec![0; self.id as usize];
+        self.g.values().flatten().for_each(|&v| inputs[v as usize] += 1);
+        let mut ready: Vec<u32> = (0..self.id).filter(|&u| inputs[u as usize] == 0).collect();
+        let mut cycles = Vec::new();
+        loop {
+            while let Some(u) = ready.pop() {
+                for &v in self.g.get(&u).into_iter().flatten() {
+                    if inputs[v as usize] > 0 {
+                        inputs[v as usize] -= 1;
+                        if inputs[v as usize] == 0 {
+                            ready.push(v);
+                        }
+                    }
+                }
+            }
+            let left = |u: u32| inputs[u as usize] > 0;
+            let Some(mut u) = (0..self.id).find(|&u| left(u)) else {
+                return cycles;
+            };
+            let input = |u: u32| (0..self.id).find(|&p| left(p) && self.g.get(&p).is_some_and(|vs| vs.contains(&u)));
+            let mut seen = vec![false; self.id as usize];
+            while !seen[u as usize] {
+                seen[u as usize] = true;
+                u = input(u).expect("every device left has an input left");
+            }
+            let mut cycle = vec![u];
+            let mut p = input(u).expect("every device left has an input left");
+            while p != u {
+                cycle.push(p);
+                p = input(p).expect("every device left has an input left");
+            }
+            cycle.reverse();
+            for &v in &cycle {
+                inputs[v as usize] = 0;
+                ready.push(v);
+            }
+            cycles.push(cycle.into_iter().map(|v| names[v as usize]).collect());
+        }
+    }
+
+    fn solve_part1(&mut self) -> Answer {
+        let you_id = self.get_id(String::from("you"));
+        let out_id = self.get_id(String::from("out"));
+        self.dfs2(you_id, out_id, &[], &mut HashMap::new())
+    }
+
+    fn solve_part2(&mut self) -> Answer {
+        let svr_id = self.get_id(String::from("svr"));
+        let out_id = self.get_id(String::from("out"));
+        let dac_id = self.get_id(String::from("dac"));
+        let fft_id = self.get_id(String::from("fft"));
+
+        let svr_to_fft = self.dfs2(svr_id, fft_id, &[dac_id, out_id], &mut HashMap::new());
+        let fft_to_dac = self.dfs2(fft_id, dac_id, &[svr_id, out_id], &mut HashMap::new());
+        let dac_to_out = self.dfs2(dac_id, out_id, &[svr_id, fft_id], &mut HashMap::new());
+
+        let svr_to_dac = self.dfs2(svr_id, dac_id, &[fft_id, out_id], &mut HashMap::new());
+        let dac_to_fft = self.dfs2(dac_id, fft_id, &[svr_id, out_id], &mut HashMap::new());
+        let fft_to_out = self.dfs2(fft_id, out_id, &[svr_id, dac_id], &mut HashMap::new());
+
+        svr_to_fft * fft_to_dac * dac_to_out + svr_to_dac * dac_to_fft * fft_to_out
+    }
+
+    fn dfs2(&self, src: u32, dst: u32, nodes_to_exclude: &[u32], memo: &mut HashMap<u32, Answer>) -> Answer {
+        if nodes_to_exclude.contains(&src) {
+            return Answer::zero();
+        }
+
+        if src == dst {
+            return Answer::one();
+        }
+
+        if let Some(res) = memo.get(&src) {
+            return res.clone();
+        }
+
+        let mut res = Answer::zero();
+        if let Some(neighbors) = self.g.get(&src) {
+            for &neighbor in neighbors {
+                res += self.dfs2(neighbor, dst, nodes_to_exclude, memo);
+            }
+        }
+        memo.insert(src, res.clone());
+        res
+    }
+
+    fn reversed(&self) -> Graph {
+        let mut g: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (&u, vs) in &self.g {
+            for &v in vs {
+                g.entry(v).or_default().push(u);
+            }
+        }
+        Graph { g, m: self.m.clone(), id: self.id }
+    }
+
+    /// Devices by name, with the five special ones highlighted and every edge labelled with
+    /// the number of paths from `src` to `out` that use it.
+    fn to_dot(&self, src: &str) -> Dot {
+        let highlights = [
+            ("you", "lightblue"),
+            ("svr", "lightblue"),
+            ("out", "palegreen"),
+            ("dac", "gold"),
+            ("fft", "gold"),
+        ];
+        let mut names = vec![String::new(); self.id as usize];
+        for (name, &id) in &self.m {
+            names[id as usize] = name.clone();
+        }
+
+        let mut dot = Dot::digraph("devices");
+        dot.attr("rankdir", "LR");
+        for (name, colour) in highlights {
+            if self.m.contains_key(name) {
+                dot.node(name, &[("style", "filled"), ("fillcolor", colour), ("penwidth", "2")]);
+            }
+        }
+        let (Some(&src_id), Some(&out_id)) = (self.m.get(src), self.m.get("out")) else {
+            return dot;
+        };
+        let reversed = self.reversed();
+        let (mut from_src, mut to_out) = (HashMap::new(), HashMap::new());
+        let mut edges: Vec<(u32, u32)> = self.g.iter().flat_map(|(&u, vs)| vs.iter().map(move |&v| (u, v))).collect();
+        edges.sort();
+        for (u, v) in edges {
+            let paths = reversed.dfs2(u, src_id, &[], &mut from_src) * self.dfs2(v, out_id, &[], &mut to_out);
+            dot.edge(&names[u as usize], &names[v as usize], &[("label", &paths.to_string())]);
+        }
+        dot
+    }
+}
+
+fn device(input: Input) -> PResult<(String, Vec<String>)> {
+    labelled(map(word(), String::from), map(word(), String::from))(input)
+}
+
+fn parse<R: BufRead>(reader: R) -> Result<Graph, Box<dyn Error>> {
+    let mut graph = Graph::new();
+    for (u, vs) in parse_lines(reader, device)? {
+        for v in vs {
+            graph.insert(u.clone(), v);
+        }
+    }
+    if let Some(cycle) = graph.cycles().first() {
+        return Err(format!("the devices loop back through {}", cycle[0]).into());
+    }
+    Ok(graph)
+}
+
+/// One `device: outputs...` line per device, each device listed once, with no loops.
+pub fn validate(input: &str) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut graph = Graph::new();
+    let mut listed: HashMap<String, usize> = HashMap::new();
+    for (line_no, line) in validate::lines(input) {
+        match parse_line(line_no, line, device) {
+            Ok((u, vs)) => {
+                if let Some(first) = listed.get(&u) {
+                    violations.push(Violation::line(line_no, format!("device {} is already listed on line {}", u, first)));
+                    continue;
+                }
+                listed.insert(u.clone(), line_no);
+                for v in vs {
+                    graph.insert(u.clone(), v);
+                }
+            }
+            Err(err) => violations.push(err.into()),
+        }
+    }
+    for mut cycle in graph.cycles() {
+        // Start from the device listed first, so the loop is reported on its line.
+        let first = (0..cycle.len()).min_by_key(|&i| listed[cycle[i]]).unwrap_or(0);
+        cycle.rotate_left(first);
+        let line = listed[cycle[0]];
+        cycle.push(cycle[0]);
+        violations.push(Violation::line(line, format!("the devices loop: {}", cycle.join(" -> "))));
+    }
+    violations
+}
+
+// Follows every path from `src`, counting those that reach `out` after visiting all of `via`.
+fn count_paths<'a>(
+    outputs: &HashMap<&'a str, Vec<&'a str>>,
+    src: &'a str,
+    via: &[&str],
+    path: &mut Vec<&'a str>,
+) -> Result<u64, Box<dyn Error>> {
+    if path.contains(&src) {
+        return Err(format!("cycle through {}", src).into());
+    }
+    if src == "out" {
+        return Ok(if via.iter().all(|v| path.contains(v)) { 1 } else { 0 });
+    }
+    path.push(src);
+    let mut total = 0;
+    for next in outputs.get(src).into_iter().flatten() {
+        total += count_paths(outputs, next, via, path)?;
+    }
+    path.pop();
+    Ok(total)
+}
+
+/// Brute force for both parts: enumerates every path instead of memoising counts.
+pub fn reference(input: &str) -> Result<(Answer, Answer), Box<dyn Error>> {
+    let devices = parse_lines(input.as_bytes(), device)?;
+    let outputs: HashMap<&str, Vec<&str>> = devices
+        .iter()
+        .map(|(u, vs)| (u.as_str(), vs.iter().map(|v| v.as_str()).collect()))
+        .collect();
+    let part1 = count_paths(&outputs, "you", &[], &mut Vec::new())?;
+    let part2 = count_paths(&outputs, "svr", &["dac", "fft"], &mut Vec::new())?;
+    Ok((Answer::from(part1), Answer::from(part2)))
+}
+
+pub fn part1<R: BufRead>(reader: R, _r: i32) -> Result<Answer, Box<dyn Error>> {
+    Ok(parse(reader)?.solve_part1())
+}
+
+pub fn part2<R: BufRead>(reader: R, dot_path: Option<&Path>) -> Result<Answer, Box<dyn Error>> {
+    let mut graph = parse(reader)?;
+    dot::export(dot_path, || graph.to_dot("svr"))?;
+    Ok(graph.solve_part2())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimised fuzz inputs, also in fuzz/corpus/day11.
+    #[test]
+    fn devices_in_a_loop() {
+        assert!(part1("you: aaa out\naaa: you\n".as_bytes(), 0).is_err());
+        assert!(part2("svr: dac\ndac: fft\nfft: dac out\n".as_bytes(), None).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+// The solutions, one module per day. Each exposes `part1` and `part2` over any
+// `BufRead`, and `validate` for the invariants its input must meet; the binaries
+// in `src/bin` run them on the example and the input.
+
+use std::error::Error;
+use crate::validate::Violation;
+use crate::visualize::Output;
+
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+
+/// A part's answer on a whole input, as the binaries would print it.
+pub type Part = fn(&str) -> Result<String, Box<dyn Error>>;
+
+/// What a runner needs to know about a day. The parts take the same extra arguments as in the
+/// day's binary.
+pub struct Day {
+    pub day: &'static str,
+    pub validate: fn(&str) -> Vec<Violation>,
+    pub part1: Part,
+    pub part2: Part,
+}
+
+impl Day {
+    pub fn input_file(&self) -> String {
+        format!("input/{}.txt", self.day)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: "04",
+        validate: day04::validate,
+        part1: |input| Ok(day04::part1(input.as_bytes())?.to_string()),
+        part2: |input| Ok(day04::part2(input.as_bytes(), Output::default(), false)?.to_string()),
+    },
+    Day {
+        day: "05",
+        validate: day05::validate,
+        part1: |input| Ok(day05::part1(input.as_bytes())?.to_string()),
+        part2: |input| Ok(day05::part2(input.as_bytes())?.to_string()),
+    },
+    Day {
+        day: "06",
+        validate: day06::validate,
+        part1: |input| Ok(day06::part1(input.as_bytes())?.to_string()),
+        part2: |input| Ok(day06::part2(input.as_bytes())?.to_string()),
+    },
+    Day {
+        day: "07",
+        validate: day07::validate,
+        part1: |input| Ok(day07::part1(input.as_bytes())?.to_string()),
+        part2: |input| Ok(day07::part2(input.as_bytes(), Output::default(), false)?.to_string()),
+    },
+    Day {
+        day: "08",
+        validate: day08::validate,
+        part1: |input| Ok(day08::part1(input.as_bytes(), 1000, None)?.to_string()),
+        part2: |input| Ok(day08::part2(input.as_bytes())?.to_string()),
+    },
+    Day {
+        day: "09",
+        validate: day09::validate,
+        part1: |input| Ok(day09::part1(input.as_bytes(), 1000)?.to_string()),
+        part2: |input| Ok(day09::part2(input.as_bytes(), Output::default())?.to_string()),
+    },
+    Day {
+        day: "10",
+        validate: day10::validate,
+        part1: |input| Ok(day10::part1(input.as_bytes(), 1000, false)?.to_string()),
+        part2: |input| Ok(day10::part2(input.as_bytes(), day10::Backend::Exact, day10::Solving::default())?.to_string()),
+    },
+    Day {
+        day: "11",
+        validate: day11::validate,
+        part1: |input| Ok(day11::part1(input.as_bytes(), 1000)?.to_string()),
+        part2: |input| Ok(day11::part2(input.as_bytes(), None)?.to_string()),
+    },
+];
+
+/// The day numbered `day`, e.g. `9` or `09`.
+pub fn find(day: &str) -> Option<&'static Day> {
+    let n: u32 = day.parse().ok()?;
+    DAYS.iter().find(|d| d.day.parse() == Ok(n))
+}
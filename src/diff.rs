@@ -0,0 +1,202 @@
+// Differential testing: a day's fast solver against a simple brute-force
+// reference on generated inputs. Inputs grow from small to `max_size`; the
+// first input on which the two disagree is shrunk by deleting lines for as
+// long as they still disagree, and reported.
+//
+// Days run it with `--diff[=CASES]`, optionally with `--seed=N` and
+// `--max-size=N`. The reference is the judge: it should reject inputs it
+// does not consider valid, and those never count as a mismatch. Panics are
+// caught and count as errors; `quiet_panics` keeps their messages off stderr.
+
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use crate::gen::{self, GenRng};
+use crate::runner;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+    pub max_size: usize,
+}
+
+impl Config {
+    /// Reads `--diff[=CASES]`, `--seed=N` and `--max-size=N`; `None` without `--diff`. Fails on a
+    /// value that is not a number.
+    pub fn from_args() -> Result<Option<Self>, String> {
+        let mut config = None;
+        let (mut seed, mut max_size) = (0, 3);
+        for arg in std::env::args() {
+            if arg == "--diff" {
+                config = config.or(Some(100));
+            } else if let Some(n) = arg.strip_prefix("--diff=") {
+                config = Some(n.parse().map_err(|e| format!("{}: {}", arg, e))?);
+            } else if let Some(n) = arg.strip_prefix("--seed=") {
+                seed = n.parse().map_err(|e| format!("{}: {}", arg, e))?;
+            } else if let Some(n) = arg.strip_prefix("--max-size=") {
+                max_size = n.parse().map_err(|e| format!("{}: {}", arg, e))?;
+            }
+        }
+        Ok(config.map(|cases| Self { cases, seed, max_size }))
+    }
+
+    // Case `i` gets its own seed and a size growing from 1 to `max_size`.
+    fn case(&self, i: usize) -> (u64, usize) {
+        (self.seed + i as u64, 1 + i * self.max_size.saturating_sub(1) / self.cases.max(1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch<T> {
+    pub seed: u64,
+    pub size: usize,
+    /// The generated input, shrunk.
+    pub input: String,
+    pub fast: Result<T, String>,
+    pub reference: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for Mismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "mismatch (seed {}, size {}) on:", self.seed, self.size)?;
+        write!(f, "{}", self.input)?;
+        writeln!(f, "fast:      {:?}", self.fast)?;
+        write!(f, "reference: {:?}", self.reference)
+    }
+}
+
+/// Runs `solve`, turning a panic into an error.
+pub fn attempt<T>(solve: impl FnOnce() -> Result<T, Box<dyn Error>>) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", runner::panic_message(payload))),
+    }
+}
+
+/// Runs `f` with panic messages kept off stderr, then puts the previous panic hook back.
+/// A panic that escapes `f` is raised again once the hook is restored.
+pub fn quiet_panics<T>(f: impl FnOnce() -> T) -> T {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(previous);
+    result.unwrap_or_else(|payload| panic!("{}", runner::panic_message(payload)))
+}
+
+// The reference accepts `input` and the fast solver does not agree with it.
+fn disagreement<T: PartialEq>(
+    input: &str,
+    fast: &impl Fn(&str) -> Result<T, Box<dyn Error>>,
+    reference: &impl Fn(&str) -> Result<T, Box<dyn Error>>,
+) -> Option<(Result<T, String>, T)> {
+    let expected = attempt(|| reference(input)).ok()?;
+    let actual = attempt(|| fast(input));
+    match &actual {
+        Ok(v) if *v == expected => None,
+        _ => Some((actual, expected)),
+    }
+}
+
+/// Deletes runs of lines from `input`, longest first, for as long as `fails` holds.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len().div_ceil(2).max(1);
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = lines[..start].iter().chain(&lines[end..]).copied().collect();
+            let text: String = candidate.iter().map(|line| format!("{}\n", line)).collect();
+            if !candidate.is_empty() && fails(&text) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start += 1;
+            }
+        }
+        if chunk == 1 && !removed {
+            break;
+        }
+        if !removed {
+            chunk = chunk.div_ceil(2);
+        }
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Compares `fast` with `reference` on `config.cases` generated inputs. Returns how many
+/// cases the reference accepted, or the first mismatch, shrunk.
+pub fn run<T: PartialEq>(
+    config: &Config,
+    generate: impl Fn(&mut GenRng, usize) -> String,
+    fast: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+    reference: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+) -> Result<usize, Mismatch<T>> {
+    let mut checked = 0;
+    let mut mismatch = None;
+    for i in 0..config.cases {
+        let (seed, size) = config.case(i);
+        let input = generate(&mut gen::rng(seed), size);
+        if attempt(|| reference(&input)).is_err() {
+            continue;
+        }
+        checked += 1;
+        if disagreement(&input, &fast, &reference).is_some() {
+            let input = shrink(&input, |text| disagreement(text, &fast, &reference).is_some());
+            let (fast, reference) = disagreement(&input, &fast, &reference).unwrap();
+            mismatch = Some(Mismatch { seed, size, input, fast, reference });
+            break;
+        }
+    }
+    match mismatch {
+        Some(m) => Err(m),
+        None => Ok(checked),
+    }
+}
+
+/// [`run`], printing the outcome; a mismatch is an error.
+pub fn check<T: PartialEq + fmt::Debug>(
+    config: &Config,
+    generate: impl Fn(&mut GenRng, usize) -> String,
+    fast: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+    reference: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    match run(config, generate, fast, reference) {
+        Ok(checked) => {
+            println!("{} of {} generated inputs checked, all agree", checked, config.cases);
+            Ok(())
+        }
+        Err(mismatch) => Err(mismatch.to_string().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn finds_and_shrinks_a_mismatch() {
+        let config = Config { cases: 50, seed: 0, max_size: 5 };
+        let generate = |rng: &mut GenRng, size: usize| -> String {
+            (0..size * 4).map(|_| format!("{}\n", rng.random_range(0..10))).collect()
+        };
+        let reference = |input: &str| -> Result<u32, Box<dyn Error>> {
+            Ok(input.lines().map(|l| l.parse::<u32>()).sum::<Result<u32, _>>()?)
+        };
+        // Wrong whenever a 7 is present, and panics on a 9.
+        let fast = |input: &str| -> Result<u32, Box<dyn Error>> {
+            let numbers: Vec<u32> = input.lines().map(|l| l.parse().unwrap()).collect();
+            assert!(!numbers.contains(&9));
+            Ok(numbers.iter().filter(|&&n| n != 7).sum())
+        };
+
+        let mismatch = quiet_panics(|| run(&config, generate, fast, reference)).unwrap_err();
+        assert_eq!(1, mismatch.input.lines().count());
+        assert!(["7\n", "9\n"].contains(&mismatch.input.as_str()));
+
+        assert_eq!(Ok(50), run(&config, generate, reference, reference));
+    }
+}
@@ -0,0 +1,162 @@
+// Graphviz DOT output for the graph days. Days build a `Dot` in a closure
+// passed to `export`, which only runs it when given a path; the binaries read
+// it from `--dot=PATH` and pass it for the real input only.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+type Attrs = Vec<(String, String)>;
+
+fn attrs(list: &[(&str, &str)]) -> Attrs {
+    list.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_attrs(f: &mut fmt::Formatter<'_>, attrs: &Attrs) -> fmt::Result {
+    if attrs.is_empty() {
+        return Ok(());
+    }
+    let list: Vec<String> = attrs.iter().map(|(k, v)| format!("{}={}", k, quote(v))).collect();
+    write!(f, " [{}]", list.join(", "))
+}
+
+#[derive(Debug, Clone)]
+struct Cluster {
+    label: String,
+    nodes: Vec<(String, Attrs)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    graph: Attrs,
+    nodes: Vec<(String, Attrs)>,
+    clusters: Vec<Cluster>,
+    edges: Vec<(String, String, Attrs)>,
+}
+
+impl Dot {
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            graph: Vec::new(),
+            nodes: Vec::new(),
+            clusters: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn digraph(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    pub fn graph(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    /// Attributes of the whole graph, e.g. `("rankdir", "LR")`.
+    pub fn attr(&mut self, key: &str, value: &str) -> &mut Self {
+        self.graph.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn node(&mut self, id: &str, list: &[(&str, &str)]) -> &mut Self {
+        self.nodes.push((id.to_string(), attrs(list)));
+        self
+    }
+
+    /// A labelled box around `nodes`.
+    pub fn cluster(&mut self, label: &str, nodes: &[(&str, &[(&str, &str)])]) -> &mut Self {
+        let nodes = nodes.iter().map(|(id, list)| (id.to_string(), attrs(list))).collect();
+        self.clusters.push(Cluster { label: label.to_string(), nodes });
+        self
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, list: &[(&str, &str)]) -> &mut Self {
+        self.edges.push((from.to_string(), to.to_string(), attrs(list)));
+        self
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        writeln!(f, "{} {} {{", kind, quote(&self.name))?;
+        for (k, v) in &self.graph {
+            writeln!(f, "    {}={};", k, quote(v))?;
+        }
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            writeln!(f, "    subgraph cluster_{} {{", i)?;
+            writeln!(f, "        label={};", quote(&cluster.label))?;
+            for (id, attrs) in &cluster.nodes {
+                write!(f, "        {}", quote(id))?;
+                write_attrs(f, attrs)?;
+                writeln!(f, ";")?;
+            }
+            writeln!(f, "    }}")?;
+        }
+        for (id, attrs) in &self.nodes {
+            write!(f, "    {}", quote(id))?;
+            write_attrs(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        for (from, to, attrs) in &self.edges {
+            write!(f, "    {} {} {}", quote(from), arrow, quote(to))?;
+            write_attrs(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// The PATH of `--dot=PATH`, if given.
+pub fn export_path() -> Option<PathBuf> {
+    std::env::args().find_map(|arg| arg.strip_prefix("--dot=").map(PathBuf::from))
+}
+
+/// Builds the graph and writes it to `path`, if there is one.
+pub fn export(path: Option<&Path>, build: impl FnOnce() -> Dot) -> io::Result<()> {
+    match path {
+        Some(path) => build().save(path),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters_and_escaping() {
+        let mut dot = Dot::graph("circuits");
+        dot.attr("rankdir", "LR")
+            .cluster("circuit 1", &[("a", &[]), ("b", &[("label", "say \"b\"")])])
+            .node("c", &[])
+            .edge("a", "b", &[("label", "#1")]);
+        let expected = "\
+graph \"circuits\" {
+    rankdir=\"LR\";
+    subgraph cluster_0 {
+        label=\"circuit 1\";
+        \"a\";
+        \"b\" [label=\"say \\\"b\\\"\"];
+    }
+    \"c\";
+    \"a\" -- \"b\" [label=\"#1\"];
+}
+";
+        assert_eq!(expected, dot.to_string());
+        assert!(Dot::digraph("g").edge("a", "b", &[]).to_string().contains("\"a\" -> \"b\";"));
+    }
+}
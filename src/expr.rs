@@ -0,0 +1,248 @@
+// Checked integer expressions over a table of binary operators, used to
+// evaluate Day 6 worksheet problems.
+//
+// An operator row holds either a single operator, applied between every pair of
+// numbers (`*` under `2 3 4` is 2 * 3 * 4), or one operator per gap between the
+// numbers (`+ * -` under `1 2 3 4` is 1 + 2 * 3 - 4), evaluated with the usual
+// precedence. All arithmetic is checked; overflow is an error rather than a wrap,
+// and `evaluate_row_answer` retries an overflowing problem with big integers.
+
+use std::error::Error;
+use std::fmt;
+use num_bigint::BigInt;
+use crate::answer::Answer;
+
+#[derive(Debug)]
+pub struct Operator {
+    pub symbol: &'static str,
+    pub precedence: u8,
+    pub right_assoc: bool,
+    apply: fn(i128, i128) -> Result<i128, EvalError>,
+    apply_big: fn(BigInt, BigInt) -> Result<BigInt, EvalError>,
+}
+
+// Big powers are refused beyond this many bits rather than computed.
+const MAX_POW_BITS: u64 = 1 << 20;
+
+fn checked(symbol: &'static str, lhs: i128, rhs: i128, v: Option<i128>) -> Result<i128, EvalError> {
+    v.ok_or(EvalError::Overflow { op: symbol, lhs, rhs })
+}
+
+pub const OPERATORS: &[Operator] = &[
+    Operator {
+        symbol: "max",
+        precedence: 0,
+        right_assoc: false,
+        apply: |a, b| Ok(a.max(b)),
+        apply_big: |a, b| Ok(a.max(b)),
+    },
+    Operator {
+        symbol: "min",
+        precedence: 0,
+        right_assoc: false,
+        apply: |a, b| Ok(a.min(b)),
+        apply_big: |a, b| Ok(a.min(b)),
+    },
+    Operator {
+        symbol: "+",
+        precedence: 1,
+        right_assoc: false,
+        apply: |a, b| checked("+", a, b, a.checked_add(b)),
+        apply_big: |a, b| Ok(a + b),
+    },
+    Operator {
+        symbol: "-",
+        precedence: 1,
+        right_assoc: false,
+        apply: |a, b| checked("-", a, b, a.checked_sub(b)),
+        apply_big: |a, b| Ok(a - b),
+    },
+    Operator {
+        symbol: "*",
+        precedence: 2,
+        right_assoc: false,
+        apply: |a, b| checked("*", a, b, a.checked_mul(b)),
+        apply_big: |a, b| Ok(a * b),
+    },
+    Operator {
+        symbol: "/",
+        precedence: 2,
+        right_assoc: false,
+        apply: |a, b| match b {
+            0 => Err(EvalError::DivisionByZero),
+            _ => checked("/", a, b, a.checked_div(b)),
+        },
+        apply_big: |a, b| match b == BigInt::ZERO {
+            true => Err(EvalError::DivisionByZero),
+            false => Ok(a / b),
+        },
+    },
+    Operator {
+        symbol: "^",
+        precedence: 3,
+        right_assoc: true,
+        apply: |a, b| match u32::try_from(b) {
+            Ok(exp) => checked("^", a, b, a.checked_pow(exp)),
+            Err(_) => Err(EvalError::InvalidExponent(b.to_string())),
+        },
+        apply_big: |a, b| match u32::try_from(&b) {
+            Ok(exp) if a.bits() * exp as u64 <= MAX_POW_BITS => Ok(a.pow(exp)),
+            _ => Err(EvalError::InvalidExponent(b.to_string())),
+        },
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    UnknownOperator(String),
+    Overflow { op: &'static str, lhs: i128, rhs: i128 },
+    DivisionByZero,
+    /// Negative, or too large to compute.
+    InvalidExponent(String),
+    /// The operator row has neither one operator nor one per gap between numbers.
+    Arity { numbers: usize, operators: usize },
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnknownOperator(op) => write!(f, "unknown operator '{}'", op),
+            EvalError::Overflow { op, lhs, rhs } => write!(f, "overflow evaluating {} {} {}", lhs, op, rhs),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::InvalidExponent(exp) => write!(f, "invalid exponent {}", exp),
+            EvalError::Arity { numbers, operators } => {
+                write!(f, "{} operators cannot combine {} numbers", operators, numbers)
+            }
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+pub fn operator(symbol: &str) -> Result<&'static Operator, EvalError> {
+    OPERATORS
+        .iter()
+        .find(|op| op.symbol == symbol)
+        .ok_or(EvalError::UnknownOperator(symbol.to_string()))
+}
+
+impl Operator {
+    pub fn apply(&self, lhs: i128, rhs: i128) -> Result<i128, EvalError> {
+        (self.apply)(lhs, rhs)
+    }
+}
+
+// Shunting-yard over any value type, so the same precedence rules serve `i128` and `BigInt`.
+fn shunting_yard<T>(
+    numbers: Vec<T>,
+    ops: &[&str],
+    apply: impl Fn(&Operator, T, T) -> Result<T, EvalError>,
+) -> Result<T, EvalError> {
+    if numbers.is_empty() || ops.len() + 1 != numbers.len() {
+        return Err(EvalError::Arity { numbers: numbers.len(), operators: ops.len() });
+    }
+    let ops = ops.iter().map(|s| operator(s)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut numbers = numbers.into_iter();
+    let mut values = vec![numbers.next().unwrap()];
+    let mut pending: Vec<&Operator> = Vec::new();
+    let reduce = |values: &mut Vec<T>, op: &Operator| -> Result<(), EvalError> {
+        let rhs = values.pop().unwrap();
+        let lhs = values.pop().unwrap();
+        values.push(apply(op, lhs, rhs)?);
+        Ok(())
+    };
+    for (op, n) in ops.into_iter().zip(numbers) {
+        while let Some(top) = pending.last() {
+            if top.precedence > op.precedence || (top.precedence == op.precedence && !op.right_assoc) {
+                reduce(&mut values, pending.pop().unwrap())?;
+            } else {
+                break;
+            }
+        }
+        pending.push(op);
+        values.push(n);
+    }
+    while let Some(op) = pending.pop() {
+        reduce(&mut values, op)?;
+    }
+    Ok(values.pop().unwrap())
+}
+
+/// Evaluates `numbers[0] ops[0] numbers[1] ops[1] ...` with operator precedence.
+pub fn evaluate(numbers: &[i128], ops: &[&str]) -> Result<i128, EvalError> {
+    shunting_yard(numbers.to_vec(), ops, |op, a, b| (op.apply)(a, b))
+}
+
+/// Like [`evaluate`], with arbitrary-precision arithmetic.
+pub fn evaluate_big(numbers: &[i128], ops: &[&str]) -> Result<BigInt, EvalError> {
+    let numbers = numbers.iter().map(|&n| BigInt::from(n)).collect();
+    shunting_yard(numbers, ops, |op, a, b| (op.apply_big)(a, b))
+}
+
+fn row_operators<'a>(row: &'a str, numbers: &[i128]) -> Result<Vec<&'a str>, EvalError> {
+    let ops: Vec<&str> = row.split_whitespace().collect();
+    match ops.as_slice() {
+        [op] => {
+            operator(op)?;
+            Ok(vec![*op; numbers.len().saturating_sub(1)])
+        }
+        _ => Ok(ops),
+    }
+}
+
+/// Evaluates a worksheet problem: the whitespace-separated operators of `row` applied to `numbers`.
+pub fn evaluate_row(row: &str, numbers: &[i128]) -> Result<i128, EvalError> {
+    evaluate(numbers, &row_operators(row, numbers)?)
+}
+
+/// Like [`evaluate_row`], but an overflowing problem is recomputed with big integers.
+pub fn evaluate_row_answer(row: &str, numbers: &[i128]) -> Result<Answer, EvalError> {
+    let ops = row_operators(row, numbers)?;
+    match evaluate(numbers, &ops) {
+        Ok(v) => Ok(Answer::from(v)),
+        Err(EvalError::Overflow { .. }) => evaluate_big(numbers, &ops).map(Answer::from),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_operator_folds() {
+        assert_eq!(Ok(33210), evaluate_row("*", &[123, 45, 6]));
+        assert_eq!(Ok(490), evaluate_row("+", &[328, 64, 98]));
+        assert_eq!(Ok(4), evaluate_row("-", &[10, 4, 2]));
+        assert_eq!(Ok(2), evaluate_row("^", &[2, 1, 3]));
+        assert_eq!(Ok(98), evaluate_row("max", &[3, 98, 64]));
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(Ok(3), evaluate(&[1, 2, 3, 4], &["+", "*", "-"]));
+        assert_eq!(Ok(2 + 512), evaluate(&[2, 2, 3, 2], &["+", "^", "^"]));
+        assert_eq!(Ok(7), evaluate(&[7, 2, 3], &["max", "+"]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(EvalError::Overflow { op: "*", lhs: i128::MAX, rhs: 2 }),
+            evaluate_row("*", &[i128::MAX, 2])
+        );
+        assert_eq!(Err(EvalError::DivisionByZero), evaluate_row("/", &[5, 0]));
+        assert_eq!(Err(EvalError::InvalidExponent(String::from("-1"))), evaluate_row("^", &[5, -1]));
+        assert_eq!(Err(EvalError::InvalidExponent(String::from("10000000"))), evaluate_row_answer("^", &[3, 10_000_000]));
+        assert_eq!(Err(EvalError::UnknownOperator(String::from("%"))), evaluate_row("%", &[5, 2]));
+        assert_eq!(Err(EvalError::Arity { numbers: 2, operators: 2 }), evaluate_row("+ +", &[1, 2]));
+    }
+
+    #[test]
+    fn overflow_falls_back_to_bigint() {
+        let answer = evaluate_row_answer("* -", &[i128::MAX, 4, i128::MAX]).unwrap();
+        assert_eq!("510423550381407695195061911147652317181", answer.to_string());
+        assert_eq!(Answer::Int(i128::MIN), evaluate_row_answer("- -", &[i128::MIN, i128::MAX, -i128::MAX]).unwrap());
+    }
+}
@@ -0,0 +1,259 @@
+// Random puzzle inputs in each day's format, for stress tests and benchmarks.
+// Generators are seeded, so the same seed and sizes always give the same text;
+// `cargo run --bin gen -- DAY` prints one. Sizes too small to fit the rest of
+// the arguments are raised, and counts that cannot fit are lowered, so every
+// generator returns.
+
+use std::collections::BTreeSet;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub type GenRng = ChaCha8Rng;
+
+pub fn rng(seed: u64) -> GenRng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Day 4: a `rows` x `cols` grid with each cell a paper roll `@` with probability `density`.
+pub fn day04(rng: &mut GenRng, rows: usize, cols: usize, density: f64) -> String {
+    let mut out = String::new();
+    for _ in 0..rows {
+        out.extend((0..cols).map(|_| if rng.random_bool(density) { '@' } else { '.' }));
+        out.push('\n');
+    }
+    out
+}
+
+/// Day 5: `ranges` fresh ID ranges below `max`, many overlapping, then `ids` ingredient IDs.
+pub fn day05(rng: &mut GenRng, ranges: usize, ids: usize, max: u64) -> String {
+    let max = max.max(2);
+    let mut out = String::new();
+    for _ in 0..ranges {
+        let start = rng.random_range(1..max);
+        let len = rng.random_range(0..=(max / ranges.max(1) as u64).max(1));
+        out += &format!("{}-{}\n", start, (start + len).min(max));
+    }
+    out.push('\n');
+    for _ in 0..ids {
+        out += &format!("{}\n", rng.random_range(1..=max));
+    }
+    out
+}
+
+/// Day 6: `problems` side by side, each `height` numbers of up to `digits` digits over a `+` or `*`.
+/// Numbers are aligned left or right within their problem, at random, and ordered by length so
+/// that the digits in each column have no gaps.
+pub fn day06(rng: &mut GenRng, problems: usize, height: usize, digits: u32) -> String {
+    let digits = digits.clamp(1, 19);
+    let mut lines = vec![String::new(); height + 1];
+    for p in 0..problems {
+        let mut numbers: Vec<String> =
+            (0..height).map(|_| rng.random_range(1..10u64.pow(digits)).to_string()).collect();
+        numbers.sort_by_key(|n| n.len());
+        if rng.random_bool(0.5) {
+            numbers.reverse();
+        }
+        let width = numbers.iter().map(|n| n.len()).max().unwrap_or(1);
+        let left = rng.random_bool(0.5);
+        for (line, n) in lines.iter_mut().zip(&numbers) {
+            if p > 0 {
+                line.push(' ');
+            }
+            *line += &if left { format!("{:<width$}", n) } else { format!("{:>width$}", n) };
+        }
+        let op = if rng.random_bool(0.5) { '+' } else { '*' };
+        let ops = &mut lines[height];
+        if p > 0 {
+            ops.push(' ');
+        }
+        *ops += &format!("{:<width$}", op);
+    }
+    lines.into_iter().map(|line| line.trim_end().to_string() + "\n").collect()
+}
+
+/// Day 7: a manifold `width` wide with `rows` rows of splitters, one in every other line.
+/// Splitters appear with probability `density`, never next to each other or on an edge.
+pub fn day07(rng: &mut GenRng, width: usize, rows: usize, density: f64) -> String {
+    let width = width.max(3);
+    let mut out = String::new();
+    let mut start = vec!['.'; width];
+    start[width / 2] = 'S';
+    out.extend(start);
+    out.push('\n');
+    for _ in 0..rows {
+        out += &".".repeat(width);
+        out.push('\n');
+        let mut row = vec!['.'; width];
+        for c in 1..width - 1 {
+            if row[c - 1] != '^' && rng.random_bool(density) {
+                row[c] = '^';
+            }
+        }
+        out.extend(row);
+        out.push('\n');
+    }
+    out += &".".repeat(width);
+    out.push('\n');
+    out
+}
+
+/// Day 8: `points` distinct junction boxes with coordinates below `max`, at most `max³` of them.
+pub fn day08(rng: &mut GenRng, points: usize, max: i64) -> String {
+    let max = max.max(1);
+    let points = points.min(usize::try_from((max as u128).pow(3)).unwrap_or(usize::MAX));
+    let mut seen = BTreeSet::new();
+    let mut out = String::new();
+    while seen.len() < points {
+        let p = (rng.random_range(0..max), rng.random_range(0..max), rng.random_range(0..max));
+        if seen.insert(p) {
+            out += &format!("{},{},{}\n", p.0, p.1, p.2);
+        }
+    }
+    out
+}
+
+/// Day 9: the red tiles of a rectilinear polygon, in order: a skyline of `steps` steps over a flat
+/// base, with coordinates below `max`, which is raised to at least `steps + 2`.
+pub fn day09(rng: &mut GenRng, steps: usize, max: i64) -> String {
+    let steps = steps.max(1);
+    // Room for `steps + 1` distinct xs in `1..max` and two heights in `0..max - 1`.
+    let max = max.max(steps as i64 + 2).max(3);
+    let mut xs = BTreeSet::new();
+    while xs.len() < steps + 1 {
+        xs.insert(rng.random_range(1..max));
+    }
+    let xs: Vec<i64> = xs.into_iter().collect();
+    let base = max;
+    let mut heights: Vec<i64> = Vec::new();
+    for _ in 0..steps {
+        // Consecutive steps differ, so no three corners are collinear.
+        let h = loop {
+            let h = rng.random_range(0..max - 1);
+            if heights.last() != Some(&h) {
+                break h;
+            }
+        };
+        heights.push(h);
+    }
+    let mut corners = vec![(xs[0], base)];
+    for (i, &h) in heights.iter().enumerate() {
+        corners.push((xs[i], h));
+        corners.push((xs[i + 1], h));
+    }
+    corners.push((xs[steps], base));
+    corners.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect()
+}
+
+/// Day 10: `machines` machines with up to `lights` lights and `buttons` buttons each. Lights and
+/// joltages come from random presses, so every machine has a solution.
+pub fn day10(rng: &mut GenRng, machines: usize, lights: usize, buttons: usize, max_presses: u64) -> String {
+    let mut out = String::new();
+    for _ in 0..machines {
+        let n = rng.random_range(2..=lights.max(2));
+        let m = rng.random_range(1..=buttons.max(1));
+        let mut wiring: Vec<Vec<usize>> = Vec::new();
+        for _ in 0..m {
+            let mut lights: Vec<usize> = (0..n).collect();
+            lights.shuffle(rng);
+            lights.truncate(rng.random_range(1..=n));
+            lights.sort();
+            wiring.push(lights);
+        }
+        let mut on = vec![false; n];
+        let mut joltage = vec![0; n];
+        for button in &wiring {
+            if rng.random_bool(0.5) {
+                button.iter().for_each(|&i| on[i] = !on[i]);
+            }
+            let presses = rng.random_range(0..=max_presses);
+            button.iter().for_each(|&i| joltage[i] += presses);
+        }
+        out.push('[');
+        out.extend(on.iter().map(|&b| if b { '#' } else { '.' }));
+        out.push(']');
+        for button in &wiring {
+            let list: Vec<String> = button.iter().map(|i| i.to_string()).collect();
+            out += &format!(" ({})", list.join(","));
+        }
+        let list: Vec<String> = joltage.iter().map(|j| j.to_string()).collect();
+        out += &format!(" {{{}}}\n", list.join(","));
+    }
+    out
+}
+
+/// Day 11: a DAG of `devices` devices besides `you`, `svr`, `dac`, `fft` and `out`, each with up
+/// to `fanout` outputs. Devices are ordered `svr`, ..., `out`, with `you`, `dac` and `fft` in
+/// between, and only connect forwards. There are at most 17571 other three-letter names.
+pub fn day11(rng: &mut GenRng, devices: usize, fanout: usize) -> String {
+    let devices = devices.min(26usize.pow(3) - 5);
+    let mut names = BTreeSet::new();
+    while names.len() < devices {
+        let name: String = (0..3).map(|_| rng.random_range(b'a'..=b'z') as char).collect();
+        if !["you", "svr", "dac", "fft", "out"].contains(&name.as_str()) {
+            names.insert(name);
+        }
+    }
+    let mut middle: Vec<String> = names.into_iter().collect();
+    middle.extend(["you", "dac", "fft"].map(String::from));
+    middle.shuffle(rng);
+    let order: Vec<String> = std::iter::once(String::from("svr")).chain(middle).chain([String::from("out")]).collect();
+
+    let mut out = String::new();
+    for (i, name) in order.iter().enumerate().take(order.len() - 1) {
+        let later = &order[i + 1..];
+        let mut outputs: Vec<&String> = later.iter().collect();
+        outputs.shuffle(rng);
+        // The next device is always an output, so every device reaches `out`.
+        let mut outputs: Vec<&String> = outputs.into_iter().take(rng.random_range(1..=fanout.max(1))).collect();
+        if !outputs.contains(&&later[0]) {
+            outputs[0] = &later[0];
+        }
+        let list: Vec<&str> = outputs.iter().map(|s| s.as_str()).collect();
+        out += &format!("{}: {}\n", name, list.join(" "));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beam::Grid;
+    use crate::worksheet::blocks;
+
+    #[test]
+    fn seeded_and_well_formed() {
+        assert_eq!(day04(&mut rng(7), 5, 8, 0.6), day04(&mut rng(7), 5, 8, 0.6));
+        assert_ne!(day04(&mut rng(7), 5, 8, 0.6), day04(&mut rng(8), 5, 8, 0.6));
+
+        let worksheet = day06(&mut rng(1), 4, 3, 3);
+        let lines: Vec<&str> = worksheet.lines().collect();
+        let blocks = blocks(&lines);
+        assert_eq!(4, blocks.len());
+        for block in blocks {
+            let (numbers, _) = block.split_last_row();
+            assert!(numbers.columns_rtl().all(|col| !col.trim().contains(' ')));
+        }
+
+        let manifold = day07(&mut rng(1), 15, 7, 0.5);
+        let streamed = crate::beam::stream_downward(manifold.as_bytes()).unwrap();
+        let lines: Vec<&str> = manifold.lines().collect();
+        assert_eq!(streamed.activated, Grid::parse(&lines).unwrap().simulate().activated.len());
+
+        let polygon = day09(&mut rng(1), 6, 100);
+        assert_eq!(14, polygon.lines().count());
+
+        let devices = day11(&mut rng(1), 20, 3);
+        assert_eq!(24, devices.lines().count());
+        assert!(devices.starts_with("svr: "));
+    }
+
+    #[test]
+    fn sizes_that_do_not_fit_are_clamped() {
+        assert_eq!(27, day08(&mut rng(1), 100, 3).lines().count());
+        assert_eq!(8, day09(&mut rng(1), 3, 1).lines().count());
+        assert_eq!(4, day09(&mut rng(1), 0, 0).lines().count());
+        assert_eq!(3, day05(&mut rng(1), 1, 1, 0).lines().count());
+        assert_eq!(2, day06(&mut rng(1), 1, 1, 0).lines().count());
+    }
+}
@@ -0,0 +1,249 @@
+// Linear algebra over GF(2), where addition is XOR. Rows are stored as bitsets
+// so that eliminating a pivot is a handful of word-wise XORs.
+
+use std::error::Error;
+use std::fmt;
+
+const WORD_BITS: usize = 64;
+
+/// Beyond this many free variables the null space is too large to enumerate.
+pub const MAX_FREE: usize = 24;
+
+/// The system has more free variables than [`MAX_FREE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyFree(pub usize);
+
+impl fmt::Display for TooManyFree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} free variables are too many to search, at most {} are supported", self.0, MAX_FREE)
+    }
+}
+
+impl Error for TooManyFree {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            words: vec![0; len.div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn from_indices(len: usize, indices: &[usize]) -> Self {
+        let mut v = Self::new(len);
+        for &i in indices {
+            v.toggle(i);
+        }
+        v
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        if self.get(i) != value {
+            self.toggle(i);
+        }
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        assert!(i < self.len, "bit {} out of range for length {}", i, self.len);
+        self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+    }
+
+    pub fn xor_assign(&mut self, other: &BitVec) {
+        for (w, o) in self.words.iter_mut().zip(other.words.iter()) {
+            *w ^= o;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |&i| self.get(i))
+    }
+}
+
+/// A system of linear equations `A·x = b` over GF(2) with `vars` unknowns.
+#[derive(Debug, Clone)]
+pub struct Gf2System {
+    vars: usize,
+    rows: Vec<(BitVec, bool)>,
+}
+
+/// Reduced row echelon form of a [`Gf2System`]: one particular solution and a
+/// basis of the null space. Every solution is `particular` XOR some subset of `null_space`.
+#[derive(Debug, Clone)]
+pub struct Gf2Solution {
+    pub particular: BitVec,
+    pub null_space: Vec<BitVec>,
+}
+
+impl Gf2System {
+    pub fn new(vars: usize) -> Self {
+        Self { vars, rows: Vec::new() }
+    }
+
+    pub fn add_equation(&mut self, coefficients: BitVec, rhs: bool) {
+        assert_eq!(coefficients.len(), self.vars);
+        self.rows.push((coefficients, rhs));
+    }
+
+    /// Gauss-Jordan elimination. Returns `None` when the system is inconsistent.
+    pub fn solve(&self) -> Option<Gf2Solution> {
+        let mut rows = self.rows.clone();
+        let mut pivots: Vec<usize> = Vec::new();
+        let mut rank = 0;
+        for col in 0..self.vars {
+            let Some(p) = (rank..rows.len()).find(|&r| rows[r].0.get(col)) else {
+                continue;
+            };
+            rows.swap(rank, p);
+            let (pivot_row, pivot_rhs) = rows[rank].clone();
+            for (r, (row, rhs)) in rows.iter_mut().enumerate() {
+                if r != rank && row.get(col) {
+                    row.xor_assign(&pivot_row);
+                    *rhs ^= pivot_rhs;
+                }
+            }
+            pivots.push(col);
+            rank += 1;
+        }
+        if rows[rank..].iter().any(|(_, rhs)| *rhs) {
+            return None;
+        }
+
+        let mut particular = BitVec::new(self.vars);
+        for (r, &col) in pivots.iter().enumerate() {
+            particular.set(col, rows[r].1);
+        }
+
+        let null_space = (0..self.vars)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut v = BitVec::new(self.vars);
+                v.set(free, true);
+                for (r, &col) in pivots.iter().enumerate() {
+                    v.set(col, rows[r].0.get(free));
+                }
+                v
+            })
+            .collect();
+
+        Some(Gf2Solution { particular, null_space })
+    }
+
+    /// The solution with the fewest ones, found by enumerating the null space, or `None` if
+    /// there is no solution. Exponential in the number of free variables, so more than
+    /// [`MAX_FREE`] of them is an error.
+    pub fn solve_min_weight(&self) -> Result<Option<BitVec>, TooManyFree> {
+        let Some(solution) = self.solve() else {
+            return Ok(None);
+        };
+        let free = solution.null_space.len();
+        if free > MAX_FREE {
+            return Err(TooManyFree(free));
+        }
+
+        let mut best = solution.particular.clone();
+        let mut current = solution.particular.clone();
+        // Walk the combinations in Gray code order so each step is a single XOR.
+        for i in 1usize..(1 << free) {
+            let flipped = i.trailing_zeros() as usize;
+            current.xor_assign(&solution.null_space[flipped]);
+            if current.count_ones() < best.count_ones() {
+                best = current.clone();
+            }
+        }
+        Ok(Some(best))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn bitvec_spans_words() {
+        let mut v = BitVec::from_indices(130, &[0, 64, 129]);
+        assert_eq!(3, v.count_ones());
+        v.xor_assign(&BitVec::from_indices(130, &[64]));
+        assert_eq!(vec![0, 129], v.ones().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn min_weight_uses_null_space() {
+        // x0 + x1 = 1, x1 + x2 = 1: solutions are {1}, {0, 2}.
+        let mut system = Gf2System::new(3);
+        system.add_equation(BitVec::from_indices(3, &[0, 1]), true);
+        system.add_equation(BitVec::from_indices(3, &[1, 2]), true);
+        let x = system.solve_min_weight().unwrap().unwrap();
+        assert_eq!(vec![1], x.ones().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn too_many_free_variables() {
+        let mut system = Gf2System::new(70);
+        system.add_equation(BitVec::from_indices(70, &(0..70).collect::<Vec<_>>()), true);
+        assert_eq!(Err(TooManyFree(69)), system.solve_min_weight());
+    }
+
+    proptest! {
+        // Day 10's lights: buttons pressed at random give a target that the fewest presses must
+        // reproduce too.
+        #[test]
+        fn min_weight_reproduces_target(
+            buttons in prop::collection::vec(prop::collection::btree_set(0usize..6, 1..4), 1..7),
+            pressed in prop::collection::vec(any::<bool>(), 7),
+        ) {
+            let press = |x: &dyn Fn(usize) -> bool| {
+                let mut on = vec![false; 6];
+                for (b, button) in buttons.iter().enumerate() {
+                    if x(b) {
+                        button.iter().for_each(|&i| on[i] ^= true);
+                    }
+                }
+                on
+            };
+            let target = press(&|b| pressed[b]);
+            let mut system = Gf2System::new(buttons.len());
+            for (light, &on) in target.iter().enumerate() {
+                let pressed_by: Vec<usize> = (0..buttons.len()).filter(|&b| buttons[b].contains(&light)).collect();
+                system.add_equation(BitVec::from_indices(buttons.len(), &pressed_by), on);
+            }
+            let x = system.solve_min_weight().unwrap().unwrap();
+            prop_assert_eq!(&target, &press(&|b| x.get(b)));
+            prop_assert!(x.count_ones() <= pressed[..buttons.len()].iter().filter(|&&p| p).count());
+        }
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        let mut system = Gf2System::new(2);
+        system.add_equation(BitVec::from_indices(2, &[0, 1]), true);
+        system.add_equation(BitVec::from_indices(2, &[0, 1]), false);
+        assert!(system.solve().is_none());
+    }
+}
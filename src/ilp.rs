@@ -0,0 +1,289 @@
+// Exact solver for small integer programs of the form
+//
+//     A·x = b,  x >= 0 integer,  minimize Σx
+//
+// with non-negative integer coefficients. Fraction-free Gaussian elimination
+// expresses the pivot variables in terms of the free ones, and the free
+// variables are then searched exhaustively within bounds implied by `b`.
+
+use std::error::Error;
+use std::fmt;
+use std::time::Instant;
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+struct Reduced {
+    // Each pivot row reads `coef * x[var] + Σ row[f] * x[f] = rhs` over the free variables f.
+    pivots: Vec<(usize, i128, i128, Vec<i128>)>,
+    free: Vec<usize>,
+}
+
+// `Ok(None)` if the system has no solution even over the rationals.
+fn reduce(a: &[Vec<u64>], b: &[u64], vars: usize) -> Result<Option<Reduced>, IlpError> {
+    let mut rows: Vec<Vec<i128>> = a
+        .iter()
+        .zip(b.iter())
+        .map(|(row, &rhs)| {
+            let mut r: Vec<i128> = row.iter().map(|&v| v as i128).collect();
+            r.push(rhs as i128);
+            r
+        })
+        .collect();
+
+    let mut pivot_cols = Vec::new();
+    let mut rank = 0;
+    for col in 0..vars {
+        let Some(p) = (rank..rows.len()).find(|&r| rows[r][col] != 0) else {
+            continue;
+        };
+        rows.swap(rank, p);
+        let pivot = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r == rank || row[col] == 0 {
+                continue;
+            }
+            let factor = row[col];
+            for (v, &pv) in row.iter_mut().zip(pivot.iter()) {
+                let scaled = v.checked_mul(pivot[col]).ok_or(IlpError::Overflow)?;
+                *v = pv.checked_mul(factor).and_then(|p| scaled.checked_sub(p)).ok_or(IlpError::Overflow)?;
+            }
+            let g = row.iter().fold(0, |g, &v| gcd(g, v));
+            if g > 1 {
+                row.iter_mut().for_each(|v| *v /= g);
+            }
+        }
+        pivot_cols.push(col);
+        rank += 1;
+    }
+    if rows[rank..].iter().any(|row| row[vars] != 0) {
+        return Ok(None);
+    }
+
+    let free: Vec<usize> = (0..vars).filter(|c| !pivot_cols.contains(c)).collect();
+    let pivots = pivot_cols
+        .iter()
+        .enumerate()
+        .map(|(r, &col)| {
+            let row = &rows[r];
+            (col, row[col], row[vars], free.iter().map(|&f| row[f]).collect())
+        })
+        .collect();
+    Ok(Some(Reduced { pivots, free }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IlpError {
+    /// An intermediate value or the sum of a solution does not fit in its integer type.
+    Overflow,
+    /// The search passed its deadline before it could prove a solution optimal.
+    TimedOut,
+}
+
+impl fmt::Display for IlpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IlpError::Overflow => write!(f, "the search overflowed"),
+            IlpError::TimedOut => write!(f, "the search ran out of time"),
+        }
+    }
+}
+
+impl Error for IlpError {}
+
+// Reading the clock on every node would dominate small searches.
+const NODES_PER_CLOCK_CHECK: u64 = 1 << 12;
+
+struct Search<'a> {
+    reduced: &'a Reduced,
+    bounds: Vec<u64>,
+    free_values: Vec<u64>,
+    best: Option<(u64, Vec<u64>)>,
+    deadline: Option<Instant>,
+    nodes: u64,
+    error: Option<IlpError>,
+}
+
+impl Search<'_> {
+    fn rec(&mut self, depth: usize, partial_sum: u64) {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(NODES_PER_CLOCK_CHECK) && self.deadline.is_some_and(|d| Instant::now() >= d) {
+            self.error = Some(IlpError::TimedOut);
+        }
+        if self.error.is_some() {
+            return;
+        }
+        if let Some((best, _)) = &self.best {
+            if partial_sum >= *best {
+                return;
+            }
+        }
+        if depth == self.reduced.free.len() {
+            self.evaluate();
+            return;
+        }
+        let bound = self.bounds[self.reduced.free[depth]];
+        for v in 0..=bound {
+            let Some(sum) = partial_sum.checked_add(v) else {
+                self.error = Some(IlpError::Overflow);
+                return;
+            };
+            self.free_values[depth] = v;
+            self.rec(depth + 1, sum);
+        }
+        self.free_values[depth] = 0;
+    }
+
+    fn evaluate(&mut self) {
+        match self.solution() {
+            Ok(Some(x)) => match x.iter().try_fold(0u64, |sum, &v| sum.checked_add(v)) {
+                Some(total) if self.best.as_ref().is_none_or(|(best, _)| total < *best) => self.best = Some((total, x)),
+                Some(_) => {}
+                None => self.error = Some(IlpError::Overflow),
+            },
+            Ok(None) => {}
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // Every variable for the current free values, or `None` if a pivot variable is fractional,
+    // negative or out of bounds.
+    fn solution(&self) -> Result<Option<Vec<u64>>, IlpError> {
+        let mut x = vec![0; self.bounds.len()];
+        for (&f, &v) in self.reduced.free.iter().zip(self.free_values.iter()) {
+            x[f] = v;
+        }
+        for (col, coef, rhs, row) in self.reduced.pivots.iter() {
+            let rest = row
+                .iter()
+                .zip(self.free_values.iter())
+                .try_fold(0i128, |sum, (&c, &v)| c.checked_mul(v as i128).and_then(|t| sum.checked_add(t)))
+                .ok_or(IlpError::Overflow)?;
+            let num = rhs.checked_sub(rest).ok_or(IlpError::Overflow)?;
+            if num % coef != 0 {
+                return Ok(None);
+            }
+            let value = num / coef;
+            if value < 0 || value > self.bounds[*col] as i128 {
+                return Ok(None);
+            }
+            x[*col] = value as u64;
+        }
+        Ok(Some(x))
+    }
+}
+
+/// Minimises `Σx` subject to `A·x = b` over non-negative integers.
+/// Returns the value of every variable, or `None` if the system has no such solution.
+pub fn minimize_sum(a: &[Vec<u64>], b: &[u64]) -> Result<Option<Vec<u64>>, IlpError> {
+    let vars = a.first().map_or(0, |row| row.len());
+    minimize_sum_with_bounds(a, b, &vec![u64::MAX; vars])
+}
+
+/// Like [`minimize_sum`], with an additional upper bound on every variable.
+pub fn minimize_sum_with_bounds(a: &[Vec<u64>], b: &[u64], upper: &[u64]) -> Result<Option<Vec<u64>>, IlpError> {
+    search(a, b, upper, None)
+}
+
+/// Like [`minimize_sum`], but gives up once `deadline` has passed.
+pub fn minimize_sum_until(a: &[Vec<u64>], b: &[u64], deadline: Instant) -> Result<Option<Vec<u64>>, IlpError> {
+    let vars = a.first().map_or(0, |row| row.len());
+    search(a, b, &vec![u64::MAX; vars], Some(deadline))
+}
+
+fn search(a: &[Vec<u64>], b: &[u64], upper: &[u64], deadline: Option<Instant>) -> Result<Option<Vec<u64>>, IlpError> {
+    assert_eq!(a.len(), b.len());
+    let vars = upper.len();
+    assert!(a.iter().all(|row| row.len() == vars));
+
+    // With non-negative coefficients no variable can exceed any right-hand side it appears in.
+    // A variable that appears nowhere only adds to the sum, so it stays at 0.
+    let bounds: Vec<u64> = (0..vars)
+        .map(|j| {
+            a.iter()
+                .zip(b.iter())
+                .filter(|(row, _)| row[j] > 0)
+                .map(|(row, &rhs)| rhs / row[j])
+                .min()
+                .unwrap_or(0)
+                .min(upper[j])
+        })
+        .collect();
+
+    let Some(reduced) = reduce(a, b, vars)? else {
+        return Ok(None);
+    };
+    let mut search = Search {
+        free_values: vec![0; reduced.free.len()],
+        reduced: &reduced,
+        bounds,
+        best: None,
+        deadline,
+        nodes: 0,
+        error: None,
+    };
+    search.rec(0, 0);
+    if let Some(e) = search.error {
+        return Err(e);
+    }
+    Ok(search.best.map(|(_, x)| x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn day10_example_machine() {
+        // Buttons (3) (1,3) (2) (2,3) (0,2) (0,1) with joltage {3,5,4,7}.
+        let a = vec![
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 0, 0, 0, 1],
+            vec![0, 0, 1, 1, 1, 0],
+            vec![1, 1, 0, 1, 0, 0],
+        ];
+        let x = minimize_sum(&a, &[3, 5, 4, 7]).unwrap().unwrap();
+        assert_eq!(10, x.iter().sum::<u64>());
+        for (row, &rhs) in a.iter().zip([3, 5, 4, 7].iter()) {
+            assert_eq!(rhs, row.iter().zip(x.iter()).map(|(c, v)| c * v).sum::<u64>());
+        }
+    }
+
+    proptest! {
+        // Day 10's joltage: buttons pressed at random give a target that the fewest presses must
+        // reproduce too.
+        #[test]
+        fn solution_reproduces_target(
+            a in prop::collection::vec(prop::collection::vec(0u64..2, 5), 1..6),
+            presses in prop::collection::vec(0u64..5, 5),
+        ) {
+            let b: Vec<u64> = a.iter().map(|row| row.iter().zip(&presses).map(|(c, v)| c * v).sum()).collect();
+            let x = minimize_sum(&a, &b).unwrap().unwrap();
+            for (row, &rhs) in a.iter().zip(&b) {
+                prop_assert_eq!(rhs, row.iter().zip(&x).map(|(c, v)| c * v).sum::<u64>());
+            }
+            prop_assert!(x.iter().sum::<u64>() <= presses.iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn infeasible() {
+        assert_eq!(Ok(None), minimize_sum(&[vec![2]], &[3]));
+        assert_eq!(Ok(None), minimize_sum(&[vec![1, 1], vec![1, 1]], &[1, 2]));
+    }
+
+    #[test]
+    fn overflow() {
+        let big = 1 << 63;
+        assert_eq!(Err(IlpError::Overflow), minimize_sum(&[vec![1, 0], vec![0, 1]], &[big, big]));
+    }
+
+    #[test]
+    fn deadline() {
+        let later = Instant::now() + std::time::Duration::from_secs(60);
+        assert_eq!(Ok(Some(vec![1, 2])), minimize_sum_until(&[vec![1, 0], vec![1, 1]], &[1, 3], later));
+        assert_eq!(Err(IlpError::TimedOut), minimize_sum_until(&[vec![1, 1, 1, 1]], &[1000], Instant::now()));
+    }
+}
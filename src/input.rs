@@ -0,0 +1,123 @@
+// Reader for puzzle inputs made of blank-line-separated sections, such as
+// Day 5's ranges followed by ingredient IDs. Line endings and trailing
+// whitespace are normalised once on read, so every part sees the same sections.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::Range;
+use crate::parse::{sections, Section};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionError {
+    Count { expected: Vec<String>, found: usize },
+    Missing(String),
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionError::Count { expected, found } => write!(
+                f,
+                "expected {} sections ({}), found {}",
+                expected.len(),
+                expected.join(", "),
+                found
+            ),
+            SectionError::Missing(name) => write!(f, "missing section {}", name),
+        }
+    }
+}
+
+impl Error for SectionError {}
+
+#[derive(Debug, Clone)]
+pub struct SectionedInput {
+    lines: Vec<String>,
+    /// Line indices of each section, split once on read.
+    spans: Vec<Range<usize>>,
+    names: Vec<String>,
+}
+
+impl SectionedInput {
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut text = String::new();
+        // `lines` already strips "\n" and "\r\n".
+        for line in reader.lines() {
+            text.push_str(line?.trim_end());
+            text.push('\n');
+        }
+        let spans = sections(&text)
+            .iter()
+            .map(|s| s.first_line - 1..s.first_line - 1 + s.lines.len())
+            .collect();
+        let lines = text.lines().map(String::from).collect();
+        Ok(Self { lines, spans, names: Vec::new() })
+    }
+
+    /// Names the sections in order, failing unless there are exactly `names.len()` of them.
+    pub fn with_names(mut self, names: &[&str]) -> Result<Self, SectionError> {
+        let expected: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let found = self.len();
+        if found != expected.len() {
+            return Err(SectionError::Count { expected, found });
+        }
+        self.names = expected;
+        Ok(self)
+    }
+
+    pub fn sections(&self) -> Vec<Section<'_>> {
+        (0..self.len()).map(|i| self.section(i)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Result<Section<'_>, SectionError> {
+        match index < self.len() {
+            true => Ok(self.section(index)),
+            false => Err(SectionError::Missing(format!("#{}", index + 1))),
+        }
+    }
+
+    fn section(&self, index: usize) -> Section<'_> {
+        let span = self.spans[index].clone();
+        Section { first_line: span.start + 1, lines: self.lines[span].iter().map(String::as_str).collect() }
+    }
+
+    pub fn named(&self, name: &str) -> Result<Section<'_>, SectionError> {
+        match self.names.iter().position(|n| n == name) {
+            Some(index) => self.get(index),
+            None => Err(SectionError::Missing(String::from(name))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{ch, pair, preceded, uint};
+
+    #[test]
+    fn crlf_and_trailing_whitespace() {
+        let text = "3-5  \r\n10-14\r\n \r\n1\r\n5\t\r\n";
+        let input = SectionedInput::read(text.as_bytes()).unwrap().with_names(&["ranges", "ids"]).unwrap();
+        let ranges = input.named("ranges").unwrap().parse(pair(uint::<u32>(), preceded(ch('-'), uint()))).unwrap();
+        assert_eq!(vec![(3, 5), (10, 14)], ranges);
+        let ids = input.named("ids").unwrap();
+        assert_eq!(4, ids.first_line);
+        assert_eq!(vec![1, 5], ids.parse(uint::<u32>()).unwrap());
+    }
+
+    #[test]
+    fn section_count_mismatch() {
+        let input = SectionedInput::read("1\n2\n".as_bytes()).unwrap();
+        let err = input.with_names(&["ranges", "ids"]).unwrap_err();
+        assert_eq!("expected 2 sections (ranges, ids), found 1", err.to_string());
+    }
+}
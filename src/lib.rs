@@ -1,3 +1,26 @@
+pub mod answer;
+pub mod automaton;
+pub mod beam;
+pub mod canvas;
+pub mod days;
+pub mod diff;
+pub mod dot;
+pub mod expr;
+pub mod gen;
+pub mod gf2;
+pub mod ilp;
+pub mod input;
+pub mod lp;
+pub mod par;
+pub mod parse;
+pub mod polygon;
+pub mod ranges;
+pub mod runner;
+pub mod union_find;
+pub mod validate;
+pub mod visualize;
+pub mod worksheet;
+
 pub fn start_day(day: &str) {
     println!("Advent of Code 2025 - Day {:0>2}", day);
 }
@@ -0,0 +1,375 @@
+// A small modelling layer for linear programs. The model is recorded as plain
+// data so it can be dumped in LP/MPS format, and handed to a solver backend
+// chosen at compile time:
+//
+// - `cbc` feature: good_lp with the COIN-OR Cbc solver (needs libCbcSolver).
+// - default: the pure-Rust exact solver in `ilp`, which accepts models of the
+//   form `A·x = b, x >= 0 integer, minimize Σx`.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Var(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sense {
+    Le,
+    Eq,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+struct VarDef {
+    name: String,
+    lower: f64,
+    upper: Option<f64>,
+    integer: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Constraint {
+    name: String,
+    terms: Vec<(Var, f64)>,
+    sense: Sense,
+    rhs: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Model {
+    name: String,
+    vars: Vec<VarDef>,
+    objective: Vec<(Var, f64)>,
+    constraints: Vec<Constraint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    Infeasible,
+    Unbounded,
+    /// The selected backend cannot handle this shape of model.
+    Unsupported(String),
+    Solver(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Infeasible => write!(f, "model is infeasible"),
+            SolveError::Unbounded => write!(f, "model is unbounded"),
+            SolveError::Unsupported(reason) => write!(f, "model not supported by {} backend: {}", BACKEND, reason),
+            SolveError::Solver(msg) => write!(f, "solver error: {}", msg),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    values: Vec<f64>,
+}
+
+impl Solution {
+    pub fn value(&self, var: Var) -> f64 {
+        self.values[var.0]
+    }
+}
+
+#[cfg(feature = "cbc")]
+pub const BACKEND: &str = "cbc";
+#[cfg(not(feature = "cbc"))]
+pub const BACKEND: &str = "exact";
+
+impl Model {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            vars: Vec::new(),
+            objective: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn add_integer(&mut self, name: &str, lower: f64, upper: Option<f64>) -> Var {
+        self.add_var(name, lower, upper, true)
+    }
+
+    pub fn add_var(&mut self, name: &str, lower: f64, upper: Option<f64>, integer: bool) -> Var {
+        self.vars.push(VarDef { name: name.to_string(), lower, upper, integer });
+        Var(self.vars.len() - 1)
+    }
+
+    pub fn minimise(&mut self, terms: Vec<(Var, f64)>) {
+        self.objective = terms;
+    }
+
+    pub fn add_constraint(&mut self, name: &str, terms: Vec<(Var, f64)>, sense: Sense, rhs: f64) {
+        self.constraints.push(Constraint { name: name.to_string(), terms, sense, rhs });
+    }
+
+    pub fn solve(&self) -> Result<Solution, SolveError> {
+        backend::solve(self)
+    }
+
+    /// CPLEX LP text format.
+    pub fn to_lp(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "\\ Model: {}", self.name).unwrap();
+        writeln!(out, "Minimize").unwrap();
+        writeln!(out, " obj: {}", self.format_terms(&self.objective)).unwrap();
+        writeln!(out, "Subject To").unwrap();
+        for c in self.constraints.iter() {
+            let op = match c.sense {
+                Sense::Le => "<=",
+                Sense::Eq => "=",
+                Sense::Ge => ">=",
+            };
+            writeln!(out, " {}: {} {} {}", c.name, self.format_terms(&c.terms), op, c.rhs).unwrap();
+        }
+        writeln!(out, "Bounds").unwrap();
+        for v in self.vars.iter() {
+            match v.upper {
+                Some(upper) => writeln!(out, " {} <= {} <= {}", v.lower, v.name, upper).unwrap(),
+                None => writeln!(out, " {} >= {}", v.name, v.lower).unwrap(),
+            }
+        }
+        let integers: Vec<&str> = self.vars.iter().filter(|v| v.integer).map(|v| v.name.as_str()).collect();
+        if !integers.is_empty() {
+            writeln!(out, "General").unwrap();
+            writeln!(out, " {}", integers.join(" ")).unwrap();
+        }
+        writeln!(out, "End").unwrap();
+        out
+    }
+
+    /// Free-format MPS.
+    pub fn to_mps(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "NAME {}", self.name).unwrap();
+        writeln!(out, "ROWS").unwrap();
+        writeln!(out, " N obj").unwrap();
+        for c in self.constraints.iter() {
+            let kind = match c.sense {
+                Sense::Le => "L",
+                Sense::Eq => "E",
+                Sense::Ge => "G",
+            };
+            writeln!(out, " {} {}", kind, c.name).unwrap();
+        }
+        writeln!(out, "COLUMNS").unwrap();
+        let mut in_integer_block = false;
+        for (i, v) in self.vars.iter().enumerate() {
+            if v.integer != in_integer_block {
+                let marker = if v.integer { "INTORG" } else { "INTEND" };
+                writeln!(out, " MARKER 'MARKER' '{}'", marker).unwrap();
+                in_integer_block = v.integer;
+            }
+            let var = Var(i);
+            let objective = self.objective.iter().filter(|(x, _)| *x == var);
+            for (_, coef) in objective {
+                writeln!(out, " {} obj {}", v.name, coef).unwrap();
+            }
+            for c in self.constraints.iter() {
+                for (_, coef) in c.terms.iter().filter(|(x, _)| *x == var) {
+                    writeln!(out, " {} {} {}", v.name, c.name, coef).unwrap();
+                }
+            }
+        }
+        if in_integer_block {
+            writeln!(out, " MARKER 'MARKER' 'INTEND'").unwrap();
+        }
+        writeln!(out, "RHS").unwrap();
+        for c in self.constraints.iter() {
+            writeln!(out, " RHS {} {}", c.name, c.rhs).unwrap();
+        }
+        writeln!(out, "BOUNDS").unwrap();
+        for v in self.vars.iter() {
+            writeln!(out, " LO BND {} {}", v.name, v.lower).unwrap();
+            if let Some(upper) = v.upper {
+                writeln!(out, " UP BND {} {}", v.name, upper).unwrap();
+            }
+        }
+        writeln!(out, "ENDATA").unwrap();
+        out
+    }
+
+    /// Writes LP or MPS depending on the extension of `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("lp") => fs::write(path, self.to_lp()),
+            Some("mps") => fs::write(path, self.to_mps()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{}: expected a .lp or .mps file", path.display()),
+            )),
+        }
+    }
+
+    fn format_terms(&self, terms: &[(Var, f64)]) -> String {
+        if terms.is_empty() {
+            return String::from("0");
+        }
+        terms
+            .iter()
+            .enumerate()
+            .map(|(i, (var, coef))| {
+                let name = &self.vars[var.0].name;
+                let sign = if *coef < 0.0 { "- " } else if i > 0 { "+ " } else { "" };
+                match coef.abs() {
+                    1.0 => format!("{}{}", sign, name),
+                    c => format!("{}{} {}", sign, c, name),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(feature = "cbc")]
+mod backend {
+    use super::*;
+    use good_lp::{variable, variables, Expression, ResolutionError, SolverModel};
+
+    pub fn solve(model: &Model) -> Result<Solution, SolveError> {
+        let mut vars = variables!();
+        let x: Vec<good_lp::Variable> = model
+            .vars
+            .iter()
+            .map(|v| {
+                let mut def = variable().min(v.lower);
+                if let Some(upper) = v.upper {
+                    def = def.max(upper);
+                }
+                if v.integer {
+                    def = def.integer();
+                }
+                vars.add(def)
+            })
+            .collect();
+        let expression = |terms: &[(Var, f64)]| -> Expression {
+            terms.iter().map(|(var, coef)| *coef * x[var.0]).sum()
+        };
+
+        let mut problem = vars.minimise(expression(&model.objective)).using(good_lp::default_solver);
+        for c in model.constraints.iter() {
+            let lhs = expression(&c.terms);
+            problem = problem.with(match c.sense {
+                Sense::Le => lhs.leq(c.rhs),
+                Sense::Eq => lhs.eq(c.rhs),
+                Sense::Ge => lhs.geq(c.rhs),
+            });
+        }
+
+        match problem.solve() {
+            Ok(solution) => Ok(Solution {
+                values: x.iter().map(|&var| good_lp::Solution::value(&solution, var)).collect(),
+            }),
+            Err(ResolutionError::Infeasible) => Err(SolveError::Infeasible),
+            Err(ResolutionError::Unbounded) => Err(SolveError::Unbounded),
+            Err(e) => Err(SolveError::Solver(e.to_string())),
+        }
+    }
+}
+
+#[cfg(not(feature = "cbc"))]
+mod backend {
+    use super::*;
+    use crate::ilp;
+
+    fn as_count(value: f64, what: &str) -> Result<u64, SolveError> {
+        if value >= 0.0 && value.fract() == 0.0 {
+            Ok(value as u64)
+        } else {
+            Err(SolveError::Unsupported(format!("{} must be a non-negative integer, got {}", what, value)))
+        }
+    }
+
+    pub fn solve(model: &Model) -> Result<Solution, SolveError> {
+        let n = model.vars.len();
+        let mut upper = Vec::with_capacity(n);
+        for v in model.vars.iter() {
+            if !v.integer || v.lower != 0.0 {
+                return Err(SolveError::Unsupported(format!("{} must be an integer with lower bound 0", v.name)));
+            }
+            upper.push(match v.upper {
+                Some(u) => u.floor().max(0.0) as u64,
+                None => u64::MAX,
+            });
+        }
+        let mut objective = vec![0.0; n];
+        for (var, coef) in model.objective.iter() {
+            objective[var.0] += coef;
+        }
+        if objective.iter().any(|&c| c != 1.0) {
+            return Err(SolveError::Unsupported(String::from("objective must be the sum of all variables")));
+        }
+
+        let mut a = Vec::with_capacity(model.constraints.len());
+        let mut b = Vec::with_capacity(model.constraints.len());
+        for c in model.constraints.iter() {
+            if c.sense != Sense::Eq {
+                return Err(SolveError::Unsupported(format!("{} must be an equality", c.name)));
+            }
+            let mut row = vec![0; n];
+            for (var, coef) in c.terms.iter() {
+                row[var.0] += as_count(*coef, &c.name)?;
+            }
+            a.push(row);
+            b.push(as_count(c.rhs, &c.name)?);
+        }
+
+        match ilp::minimize_sum_with_bounds(&a, &b, &upper) {
+            Ok(Some(x)) => Ok(Solution { values: x.into_iter().map(|v| v as f64).collect() }),
+            Ok(None) => Err(SolveError::Infeasible),
+            Err(e) => Err(SolveError::Solver(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Model {
+        let mut model = Model::new("example");
+        let x = model.add_integer("x", 0.0, Some(3.0));
+        let y = model.add_integer("y", 0.0, None);
+        model.minimise(vec![(x, 1.0), (y, 1.0)]);
+        model.add_constraint("c0", vec![(x, 1.0), (y, 2.0)], Sense::Eq, 7.0);
+        model
+    }
+
+    #[test]
+    fn lp_dump() {
+        let lp = example().to_lp();
+        assert!(lp.contains(" obj: x + y\n"));
+        assert!(lp.contains(" c0: x + 2 y = 7\n"));
+        assert!(lp.contains(" 0 <= x <= 3\n"));
+        assert!(lp.contains("General\n x y\n"));
+    }
+
+    #[test]
+    fn mps_dump() {
+        let mps = example().to_mps();
+        assert!(mps.contains(" E c0\n"));
+        assert!(mps.contains(" y c0 2\n"));
+        assert!(mps.contains(" UP BND x 3\n"));
+        assert!(mps.ends_with("ENDATA\n"));
+        assert!(example().save(Path::new("example.txt")).is_err());
+    }
+
+    #[test]
+    fn solve_reports_infeasible() {
+        let solution = example().solve().unwrap();
+        assert_eq!(4.0, solution.value(Var(0)) + solution.value(Var(1)));
+
+        let mut model = example();
+        model.add_constraint("c1", vec![(Var(0), 2.0)], Sense::Eq, 3.0);
+        assert_eq!(Err(SolveError::Infeasible), model.solve().map(|_| ()));
+    }
+}
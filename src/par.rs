@@ -0,0 +1,26 @@
+// Data-parallel loops, used by Day 9's search for the largest rectangle. With
+// the `parallel` feature they run on rayon's thread pool; without it they are
+// plain iterators, so callers need no `cfg` of their own and get the same
+// results either way. Day 10 spreads its machines over `runner`'s pool instead,
+// with or without the feature.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// `f` applied to every index below `n`, in order.
+pub fn map_range<U: Send>(n: usize, f: impl Fn(usize) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "parallel")]
+    return (0..n).into_par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return (0..n).map(f).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        assert_eq!((0..1000).map(|i| i * i).collect::<Vec<_>>(), map_range(1000, |i| i * i));
+    }
+}
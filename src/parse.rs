@@ -0,0 +1,452 @@
+// A small parser-combinator toolkit for puzzle inputs.
+//
+// A parser is any `Fn(Input) -> PResult<T>`. On success it returns the value
+// and the remaining input; on failure a `ParseError` with the line and column
+// where it stopped. As in Parsec, a parser that fails after consuming input
+// is committed: `many`, `opt` and `or` propagate that error instead of
+// backtracking, so a malformed item is reported where it goes wrong rather
+// than where the enclosing repetition happened to stop.
+
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+    offset: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    text: &'a str,
+    pos: Position,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self::at_line(text, 1)
+    }
+
+    /// Input whose first line is reported as `line` in errors.
+    pub fn at_line(text: &'a str, line: usize) -> Self {
+        Self { text, pos: Position { line, column: 1, offset: 0 } }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos.offset..]
+    }
+
+    pub fn position(&self) -> Position {
+        self.pos
+    }
+
+    fn advance(&self, bytes: usize) -> Self {
+        let mut pos = self.pos;
+        for c in self.rest()[..bytes].chars() {
+            if c == '\n' {
+                pos.line += 1;
+                pos.column = 1;
+            } else {
+                pos.column += 1;
+            }
+        }
+        pos.offset += bytes;
+        Self { text: self.text, pos }
+    }
+
+    fn found(&self) -> String {
+        match self.rest().chars().next() {
+            None | Some('\n') | Some('\r') => String::from("end of line"),
+            Some(c) => format!("'{}'", c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    offset: usize,
+}
+
+impl ParseError {
+    pub fn at(pos: Position, message: String) -> Self {
+        Self { line: pos.line, column: pos.column, message, offset: pos.offset }
+    }
+
+    pub fn expected(input: &Input, what: &str) -> Self {
+        Self::at(input.pos, format!("expected {}, found {}", what, input.found()))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub type PResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub trait Parser<'a, T>: Fn(Input<'a>) -> PResult<'a, T> {}
+
+impl<'a, T, F: Fn(Input<'a>) -> PResult<'a, T>> Parser<'a, T> for F {}
+
+fn consumed(start: &Input, err: &ParseError) -> bool {
+    err.offset > start.pos.offset
+}
+
+//region Primitives
+
+pub fn satisfy<'a>(what: &'static str, pred: impl Fn(char) -> bool) -> impl Parser<'a, char> {
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if pred(c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::expected(&input, what)),
+    }
+}
+
+pub fn ch<'a>(expected: char) -> impl Parser<'a, char> {
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if c == expected => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::expected(&input, &format!("'{}'", expected))),
+    }
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok((&input.rest()[..expected.len()], input.advance(expected.len())))
+        } else {
+            Err(ParseError::expected(&input, &format!("\"{}\"", expected)))
+        }
+    }
+}
+
+pub fn one_of<'a>(chars: &'static str) -> impl Parser<'a, char> {
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if chars.contains(c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => {
+            let options: Vec<String> = chars.chars().map(|c| format!("'{}'", c)).collect();
+            Err(ParseError::expected(&input, &options.join(" or ")))
+        }
+    }
+}
+
+fn take_while<'a>(input: Input<'a>, pred: impl Fn(char) -> bool) -> (&'a str, Input<'a>) {
+    let len = input.rest().find(|c| !pred(c)).unwrap_or(input.rest().len());
+    (&input.rest()[..len], input.advance(len))
+}
+
+/// Zero or more spaces or tabs.
+pub fn ws<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| Ok(((), take_while(input, |c| c == ' ' || c == '\t').1))
+}
+
+/// One or more spaces or tabs.
+pub fn ws1<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| match take_while(input, |c| c == ' ' || c == '\t') {
+        ("", _) => Err(ParseError::expected(&input, "whitespace")),
+        (_, rest) => Ok(((), rest)),
+    }
+}
+
+/// An identifier made of letters, digits and underscores.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| match take_while(input, |c| c.is_alphanumeric() || c == '_') {
+        ("", _) => Err(ParseError::expected(&input, "a word")),
+        (w, rest) => Ok((w, rest)),
+    }
+}
+
+fn number<'a, T: FromStr>(input: Input<'a>, signed: bool) -> PResult<'a, T>
+where
+    T::Err: fmt::Display,
+{
+    let sign = if signed && input.rest().starts_with('-') { 1 } else { 0 };
+    let (digits, _) = take_while(input.advance(sign), |c| c.is_ascii_digit());
+    if digits.is_empty() {
+        return Err(ParseError::expected(&input, "a number"));
+    }
+    let text = &input.rest()[..sign + digits.len()];
+    match text.parse() {
+        Ok(v) => Ok((v, input.advance(text.len()))),
+        Err(e) => Err(ParseError::at(input.pos, format!("invalid number {}: {}", text, e))),
+    }
+}
+
+/// An unsigned decimal integer.
+pub fn uint<'a, T: FromStr>() -> impl Parser<'a, T>
+where
+    T::Err: fmt::Display,
+{
+    move |input: Input<'a>| number(input, false)
+}
+
+/// A decimal integer with an optional leading minus sign.
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T>
+where
+    T::Err: fmt::Display,
+{
+    move |input: Input<'a>| number(input, true)
+}
+
+pub fn eof<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        if input.rest().is_empty() {
+            Ok(((), input))
+        } else {
+            Err(ParseError::expected(&input, "end of line"))
+        }
+    }
+}
+
+//endregion
+
+//region Combinators
+
+pub fn map<'a, T, U>(p: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: Input<'a>| p(input).map(|(v, rest)| (f(v), rest))
+}
+
+/// Records where `p` started, e.g. to report semantic errors at the right column.
+pub fn spanned<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, (Position, T)> {
+    move |input: Input<'a>| p(input).map(|(v, rest)| ((input.pos, v), rest))
+}
+
+pub fn pair<'a, A, B>(a: impl Parser<'a, A>, b: impl Parser<'a, B>) -> impl Parser<'a, (A, B)> {
+    move |input: Input<'a>| {
+        let (x, input) = a(input)?;
+        let (y, input) = b(input)?;
+        Ok(((x, y), input))
+    }
+}
+
+pub fn preceded<'a, A, T>(a: impl Parser<'a, A>, p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let (_, input) = a(input)?;
+        p(input)
+    }
+}
+
+pub fn terminated<'a, T, B>(p: impl Parser<'a, T>, b: impl Parser<'a, B>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let (v, input) = p(input)?;
+        let (_, input) = b(input)?;
+        Ok((v, input))
+    }
+}
+
+pub fn delimited<'a, A, T, B>(
+    open: impl Parser<'a, A>,
+    p: impl Parser<'a, T>,
+    close: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    preceded(open, terminated(p, close))
+}
+
+/// `p` followed by optional spaces, for whitespace-separated tokens.
+pub fn token<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    terminated(p, ws())
+}
+
+pub fn opt<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: Input<'a>| match p(input) {
+        Ok((v, rest)) => Ok((Some(v), rest)),
+        Err(e) if consumed(&input, &e) => Err(e),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+pub fn or<'a, T>(a: impl Parser<'a, T>, b: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input: Input<'a>| match a(input) {
+        Ok(res) => Ok(res),
+        Err(e) if consumed(&input, &e) => Err(e),
+        Err(e1) => b(input).map_err(|e2| if e2.offset >= e1.offset { e2 } else { e1 }),
+    }
+}
+
+pub fn many<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Input<'a>| {
+        let mut values = Vec::new();
+        loop {
+            match p(input) {
+                Ok((v, rest)) => {
+                    values.push(v);
+                    if rest.pos.offset == input.pos.offset {
+                        return Ok((values, rest));
+                    }
+                    input = rest;
+                }
+                Err(e) if consumed(&input, &e) => return Err(e),
+                Err(_) => return Ok((values, input)),
+            }
+        }
+    }
+}
+
+pub fn many1<'a, T>(p: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let rest = many(p);
+    move |input: Input<'a>| {
+        let (values, remaining) = rest(input)?;
+        if values.is_empty() {
+            // Re-run to report what the first item expected.
+            return Err(match rest(input) {
+                Err(e) => e,
+                Ok(_) => ParseError::expected(&input, "at least one item"),
+            });
+        }
+        Ok((values, remaining))
+    }
+}
+
+/// One or more `p` separated by `sep`. A separator must be followed by another item.
+pub fn sep_by1<'a, T, S>(p: impl Parser<'a, T>, sep: impl Parser<'a, S>) -> impl Parser<'a, Vec<T>> {
+    move |input: Input<'a>| {
+        let (first, mut input) = p(input)?;
+        let mut values = vec![first];
+        loop {
+            match sep(input) {
+                Ok((_, rest)) => {
+                    let (v, rest) = p(rest)?;
+                    values.push(v);
+                    input = rest;
+                }
+                Err(e) if consumed(&input, &e) => return Err(e),
+                Err(_) => return Ok((values, input)),
+            }
+        }
+    }
+}
+
+/// A labelled line such as `aaa: bbb ccc`, returning the key and the whitespace-separated values.
+pub fn labelled<'a, K, V>(key: impl Parser<'a, K>, value: impl Parser<'a, V>) -> impl Parser<'a, (K, Vec<V>)> {
+    pair(
+        terminated(key, pair(ch(':'), ws())),
+        move |input: Input<'a>| match input.rest().is_empty() {
+            true => Ok((Vec::new(), input)),
+            false => sep_by1(&value, gap())(input),
+        },
+    )
+}
+
+/// Whitespace between two values; fails without consuming at trailing whitespace.
+fn gap<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| match ws1()(input)? {
+        (_, rest) if rest.rest().is_empty() => Err(ParseError::expected(&input, "a value")),
+        ok => Ok(ok),
+    }
+}
+
+//endregion
+
+//region Entry points
+
+/// Parses a whole line with `p`, allowing leading and trailing whitespace.
+pub fn parse_line<'a, T>(line_no: usize, line: &'a str, p: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let (v, _) = delimited(ws(), p, pair(ws(), eof()))(Input::at_line(line, line_no))?;
+    Ok(v)
+}
+
+/// Parses every non-blank line of `reader` with `p`.
+pub fn parse_lines<R: BufRead, T>(reader: R, p: impl for<'a> Fn(Input<'a>) -> PResult<'a, T>) -> Result<Vec<T>, Box<dyn Error>> {
+    let mut values = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        values.push(parse_line(i + 1, &line, &p)?);
+    }
+    Ok(values)
+}
+
+/// A block of consecutive non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub first_line: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    pub fn parse<T>(&self, p: impl Parser<'a, T>) -> Result<Vec<T>, ParseError> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(self.first_line + i, line, &p))
+            .collect()
+    }
+}
+
+/// Splits `text` into sections separated by one or more blank lines.
+pub fn sections(text: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<Section> = None;
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            sections.extend(current.take());
+        } else {
+            current.get_or_insert(Section { first_line: i + 1, lines: Vec::new() }).lines.push(line);
+        }
+    }
+    sections.extend(current);
+    sections
+}
+
+//endregion
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_and_groups() {
+        let p = delimited(ch('('), sep_by1(uint::<u32>(), ch(',')), ch(')'));
+        assert_eq!(vec![1, 3], parse_line(1, "(1,3)", &p).unwrap());
+        assert_eq!(vec![-4, 7], parse_line(1, " -4,7 ", sep_by1(int::<i64>(), ch(','))).unwrap());
+    }
+
+    #[test]
+    fn errors_report_position() {
+        let p = delimited(ch('('), sep_by1(uint::<u32>(), ch(',')), ch(')'));
+        let err = parse_line(3, "(1,)", &p).unwrap_err();
+        assert_eq!((3, 4), (err.line, err.column));
+        assert_eq!("expected a number, found ')'", err.message);
+
+        let err = parse_line(1, "999", uint::<u8>()).unwrap_err();
+        assert!(err.message.starts_with("invalid number 999"));
+    }
+
+    #[test]
+    fn many_commits_after_consuming() {
+        let item = token(delimited(ch('('), uint::<u32>(), ch(')')));
+        let p = pair(many(&item), ch('{'));
+        assert!(parse_line(1, "(1) (2) {", &p).is_ok());
+        let err = parse_line(1, "(1) (x) {", &p).unwrap_err();
+        assert_eq!(6, err.column);
+    }
+
+    #[test]
+    fn labelled_values() {
+        let (key, values) = parse_line(1, "aaa: you hhh", labelled(word(), word())).unwrap();
+        assert_eq!("aaa", key);
+        assert_eq!(vec!["you", "hhh"], values);
+        let (_, values) = parse_line(1, "you: out \t", labelled(word(), word())).unwrap();
+        assert_eq!(vec!["out"], values);
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let text = "1-2\n3-4\n\n\n5\n6\n";
+        let s = sections(text);
+        assert_eq!(2, s.len());
+        assert_eq!(5, s[1].first_line);
+        let ranges = s[0].parse(pair(uint::<u32>(), preceded(ch('-'), uint::<u32>()))).unwrap();
+        assert_eq!(vec![(1, 2), (3, 4)], ranges);
+    }
+}
@@ -0,0 +1,284 @@
+// Rectilinear polygons given by their corner tiles, as in Day 9. The tiles
+// between and inside the corners are found on a coordinate-compressed grid, so
+// the work depends on the number of corners rather than on their coordinates.
+// Grid compaction follows https://gist.github.com/icub3d/6282ddab0b1d012ef054a9f212b12973.
+
+use std::collections::VecDeque;
+use itertools::Itertools;
+use crate::canvas::{Canvas, Rgb, Shape};
+use crate::par;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    pub row: i128,
+    pub col: i128,
+}
+
+impl Tile {
+    pub fn new(row: i128, col: i128) -> Self {
+        Self { row, col }
+    }
+
+    /// The number of tiles in the rectangle with `self` and `other` as opposite corners.
+    pub fn area(&self, other: &Tile) -> i128 {
+        let dr = self.row.abs_diff(other.row) + 1;
+        let dc = self.col.abs_diff(other.col) + 1;
+        (dr * dc) as i128
+    }
+}
+
+/// An inclusive rectangle of rows and columns, with `r1 <= r2` and `c1 <= c2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub r1: i128,
+    pub c1: i128,
+    pub r2: i128,
+    pub c2: i128,
+}
+
+impl Rect {
+    pub fn new(r1: i128, c1: i128, r2: i128, c2: i128) -> Self {
+        Self {
+            r1: r1.min(r2),
+            c1: c1.min(c2),
+            r2: r1.max(r2),
+            c2: c1.max(c2),
+        }
+    }
+}
+
+/// The compressed polygon: `tiles[r][c]` is `'b'` on the boundary, `'i'` inside and `'o'`
+/// outside, for the tiles from `rows[r]` and `cols[c]` up to the next row and column.
+pub struct Grid {
+    pub rows: Vec<i128>,
+    pub cols: Vec<i128>,
+    pub tiles: Vec<Vec<char>>,
+}
+
+impl Grid {
+    // Every coordinate is followed by the one after it, so a gap between two coordinates keeps a
+    // compressed cell of its own and a notch one tile wide cannot vanish.
+    fn get_axis(mut v: Vec<i128>) -> Vec<i128> {
+        let min = *v.iter().min().unwrap();
+        v.extend(v.iter().map(|x| x + 1).collect::<Vec<_>>());
+        v.push(min - 1);
+        v.sort();
+        v.dedup();
+        v
+    }
+
+    pub fn new(input_tiles: &[Tile]) -> Self {
+        let rows = Self::get_axis(input_tiles.iter().map(|t| t.row).collect());
+        let cols = Self::get_axis(input_tiles.iter().map(|t| t.col).collect());
+        let tiles = vec![vec!['i'; cols.len()]; rows.len()];
+        let mut grid = Self {
+            rows,
+            cols,
+            tiles,
+        };
+        grid.mark_boundary(input_tiles);
+        grid.mark_outside();
+        grid
+    }
+
+    fn mark_boundary(&mut self, input_tiles: &[Tile]) {
+        for (t1, t2) in input_tiles.iter().circular_tuple_windows() {
+            let rect = self.get_rect(t1, t2);
+            if rect.r1 == rect.r2 {
+                for c in rect.c1..=rect.c2 {
+                    self.tiles[rect.r1 as usize][c as usize] = 'b';
+                }
+            } else {
+                for r in rect.r1..=rect.r2 {
+                    self.tiles[r as usize][rect.c1 as usize] = 'b';
+                }
+            }
+        }
+    }
+
+    fn mark_outside(&mut self) {
+        let dr = [1, -1, 0, 0];
+        let dc = [0, 0, 1, -1];
+        let mut q = VecDeque::new();
+
+        let s = Tile { row: 0, col: 0 };
+        self.tiles[s.row as usize][s.col as usize] = 'o';
+        q.push_back(s);
+        while let Some(t) = q.pop_front() {
+            for i in 0..4 {
+                let nr = t.row + dr[i];
+                let nc = t.col + dc[i];
+                if nr < 0 || nc < 0 || nr >= self.rows.len() as i128 || nc >= self.cols.len() as i128 {
+                    continue;
+                }
+                if self.tiles[nr as usize][nc as usize] == 'i' {
+                    self.tiles[nr as usize][nc as usize] = 'o';
+                    q.push_back(Tile { row: nr, col: nc });
+                }
+            }
+        }
+    }
+
+    /// The compressed rectangle with corner tiles `t1` and `t2`, which must be corners of the polygon.
+    pub fn get_rect(&self, t1: &Tile, t2: &Tile) -> Rect {
+        let (r1, c1) = (
+            self.rows.binary_search(&t1.row).unwrap() as i128,
+            self.cols.binary_search(&t1.col).unwrap() as i128,
+        );
+        let (r2, c2) = (
+            self.rows.binary_search(&t2.row).unwrap() as i128,
+            self.cols.binary_search(&t2.col).unwrap() as i128,
+        );
+        Rect::new(r1, c1, r2, c2)
+    }
+
+    /// Whether every tile of the compressed `rect` is on or inside the polygon.
+    pub fn valid(&self, rect: Rect) -> bool {
+        (rect.r1..=rect.r2)
+            .cartesian_product(rect.c1..=rect.c2)
+            .all(|(r, c)| self.tiles[r as usize][c as usize] != 'o')
+    }
+
+    /// The largest rectangle with two of `corners` as opposite corners that lies within the
+    /// polygon, with its area. Each first corner is searched on its own, in parallel with the
+    /// `parallel` feature.
+    pub fn largest_inside(&self, corners: &[Tile]) -> Option<(i128, Rect)> {
+        par::map_range(corners.len(), |i| {
+            corners[i + 1..]
+                .iter()
+                .map(|t2| (corners[i].area(t2), self.get_rect(&corners[i], t2)))
+                .filter(|(_, rect)| self.valid(*rect))
+                .max_by_key(|(area, _)| *area)
+        })
+        .into_iter()
+        .flatten()
+        .max_by_key(|(area, _)| *area)
+    }
+
+    /// The compressed polygon, with `rect` outlined.
+    pub fn picture(&self, rect: Rect) -> Canvas {
+        let colours: Vec<Vec<Option<Rgb>>> = self
+            .tiles
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&t| match t {
+                        'b' => Some(Rgb(30, 110, 50)),
+                        'i' => Some(Rgb(170, 220, 170)),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut canvas = Canvas::new(self.cols.len() as f64, self.rows.len() as f64);
+        canvas.cells(&colours).add(Shape::Rect {
+            x: rect.c1 as f64,
+            y: rect.r1 as f64,
+            w: (rect.c2 - rect.c1 + 1) as f64,
+            h: (rect.r2 - rect.r1 + 1) as f64,
+            fill: None,
+            stroke: Some(Rgb::RED),
+        });
+        canvas
+    }
+
+    /// The compressed tiles with `rect` filled in as `#`.
+    pub fn highlight(&self, rect: Rect) -> Vec<Vec<char>> {
+        let mut tiles = self.tiles.clone();
+        for (r, c) in (rect.r1..=rect.r2).cartesian_product(rect.c1..=rect.c2) {
+            tiles[r as usize][c as usize] = '#';
+        }
+        tiles
+    }
+}
+
+/// The largest rectangle with two of `corners` as opposite corners, wherever it lies.
+pub fn largest(corners: &[Tile]) -> i128 {
+    corners
+        .iter()
+        .cartesian_product(corners)
+        .map(|(t1, t2)| t1.area(t2))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Everything that keeps `corners` from forming a simple rectilinear polygon, each with the index
+/// of the corner it starts at: consecutive corners must share a row or a column, turns must
+/// alternate between horizontal and vertical, and edges may only meet at their ends.
+pub fn problems(corners: &[Tile]) -> Vec<(usize, String)> {
+    if corners.len() < 4 {
+        return vec![(0, format!("{} red tiles cannot form a polygon", corners.len()))];
+    }
+    let n = corners.len();
+    let mut problems = Vec::new();
+    let edges: Vec<(Tile, Tile)> = corners.iter().copied().circular_tuple_windows().collect();
+    for (i, (a, b)) in edges.iter().enumerate() {
+        if (a.row == b.row) == (a.col == b.col) {
+            problems.push((i, format!("red tiles {} and {} are not joined by a straight line", i + 1, (i + 1) % n + 1)));
+            continue;
+        }
+        let (c, d) = edges[(i + 1) % n];
+        if (c.row == d.row) != (c.col == d.col) && (a.row == b.row) == (c.row == d.row) {
+            problems.push(((i + 1) % n, format!("red tile {} does not turn", (i + 1) % n + 1)));
+        }
+    }
+    let overlap = |(a, b): (Tile, Tile), (c, d): (Tile, Tile)| {
+        a.row.min(b.row) <= c.row.max(d.row)
+            && c.row.min(d.row) <= a.row.max(b.row)
+            && a.col.min(b.col) <= c.col.max(d.col)
+            && c.col.min(d.col) <= a.col.max(b.col)
+    };
+    for i in 0..n {
+        for j in i + 2..n {
+            if (i, j) != (0, n - 1) && overlap(edges[i], edges[j]) {
+                problems.push((i, format!("edges from red tiles {} and {} cross", i + 1, j + 1)));
+            }
+        }
+    }
+    problems
+}
+
+/// Checks that `corners` form a simple rectilinear polygon, failing with the first of [`problems`].
+pub fn check(corners: &[Tile]) -> Result<(), String> {
+    match problems(corners).into_iter().next() {
+        Some((_, problem)) => Err(problem),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use crate::gen;
+
+    fn corners(text: &str) -> Vec<Tile> {
+        text.lines()
+            .map(|line| {
+                let (col, row) = line.split_once(',').unwrap();
+                Tile::new(row.parse().unwrap(), col.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn notch_one_tile_wide() {
+        // Columns 2 and 4 are adjacent once compressed; the notch at column 3 must stay outside.
+        let corners = corners("1,9\n1,2\n2,2\n2,5\n4,5\n4,3\n5,3\n5,9\n");
+        assert_eq!(Ok(()), check(&corners));
+        assert_eq!(Some(20), Grid::new(&corners).largest_inside(&corners).map(|(area, _)| area));
+        assert_eq!(40, largest(&corners));
+    }
+
+    proptest! {
+        #[test]
+        fn inside_is_at_most_largest(seed in any::<u64>(), steps in 1usize..8, spare in 0i64..30) {
+            // The generator needs `steps + 1` distinct columns below its maximum.
+            let corners = corners(&gen::day09(&mut gen::rng(seed), steps, steps as i64 + 3 + spare));
+            prop_assert_eq!(Ok(()), check(&corners));
+            let (inside, rect) = Grid::new(&corners).largest_inside(&corners).unwrap();
+            prop_assert!(inside <= largest(&corners));
+            prop_assert!(rect.r1 <= rect.r2 && rect.c1 <= rect.c2);
+        }
+    }
+}
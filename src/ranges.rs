@@ -0,0 +1,46 @@
+// Inclusive ranges of IDs, as in Day 5's lists of fresh ingredients.
+
+/// Sorts `ranges` and merges the ones that overlap, so no value is in two of the results.
+pub fn merge(ranges: &[(u128, u128)]) -> Vec<(u128, u128)> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort();
+    let mut merged: Vec<(u128, u128)> = Vec::new();
+    for (s, e) in sorted {
+        match merged.last_mut() {
+            Some(last) if last.0 <= s && s <= last.1 => last.1 = last.1.max(e),
+            _ => merged.push((s, e)),
+        }
+    }
+    merged
+}
+
+/// How many values lie in at least one of `ranges`.
+pub fn covered(ranges: &[(u128, u128)]) -> u128 {
+    merge(ranges).iter().map(|(s, e)| e - s + 1).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+
+    #[test]
+    fn day05_example() {
+        let ranges = [(3, 5), (10, 14), (16, 20), (12, 18)];
+        assert_eq!(vec![(3, 5), (10, 20)], merge(&ranges));
+        assert_eq!(14, covered(&ranges));
+    }
+
+    proptest! {
+        #[test]
+        fn covered_counts_every_value_once(ranges in prop::collection::vec((0u128..200, 0u128..30), 0..12)) {
+            let ranges: Vec<(u128, u128)> = ranges.into_iter().map(|(s, len)| (s, s + len)).collect();
+            let values: HashSet<u128> = ranges.iter().flat_map(|&(s, e)| s..=e).collect();
+            prop_assert_eq!(values.len() as u128, covered(&ranges));
+
+            let merged = merge(&ranges);
+            prop_assert!(merged.windows(2).all(|w| w[0].1 < w[1].0));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// A fixed pool of worker threads, for `aoc run` and Day 10's machines. Jobs may
+// finish in any order; `run_ordered` hands their results back in the order the
+// jobs were given, each as soon as every job before it is done. A job that
+// panics gives back its panic message instead of taking the pool down.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+pub type Job<'a, T> = Box<dyn FnOnce() -> T + Send + 'a>;
+
+/// A job's result, or the message it panicked with.
+pub type Outcome<T> = Result<T, String>;
+
+/// One worker per CPU.
+pub fn threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// The message of a panic payload, if it has one.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+// Runs `jobs` on `threads` workers, calling `f` on this thread with each result as it comes in.
+fn pool<'a, T: Send>(jobs: Vec<Job<'a, T>>, threads: usize, mut f: impl FnMut(usize, Outcome<T>)) {
+    let n = jobs.len();
+    let jobs: Vec<_> = jobs.into_iter().map(|job| Mutex::new(Some(job))).collect();
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, n.max(1)) {
+            let (jobs, next, tx) = (&jobs, &next, tx.clone());
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i).and_then(|job| job.lock().unwrap().take()) else {
+                    break;
+                };
+                let outcome = panic::catch_unwind(AssertUnwindSafe(job)).map_err(panic_message);
+                if tx.send((i, outcome)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        for (i, result) in rx {
+            f(i, result);
+        }
+    });
+}
+
+/// Runs `jobs` on `threads` workers and calls `emit` with each result, in job order.
+pub fn run_ordered<'a, T: Send>(jobs: Vec<Job<'a, T>>, threads: usize, mut emit: impl FnMut(usize, Outcome<T>)) {
+    let mut done = BTreeMap::new();
+    let mut emitted = 0;
+    pool(jobs, threads, |i, result| {
+        done.insert(i, result);
+        while let Some(result) = done.remove(&emitted) {
+            emit(emitted, result);
+            emitted += 1;
+        }
+    });
+}
+
+/// Runs `jobs` on `threads` workers and returns their results in job order. `finished` is called
+/// as each job finishes, in whatever order that is, e.g. to advance a progress bar.
+pub fn run_all<'a, T: Send>(
+    jobs: Vec<Job<'a, T>>,
+    threads: usize,
+    mut finished: impl FnMut(usize, &Outcome<T>),
+) -> Vec<Outcome<T>> {
+    let mut results: Vec<Option<Outcome<T>>> = (0..jobs.len()).map(|_| None).collect();
+    pool(jobs, threads, |i, result| {
+        finished(i, &result);
+        results[i] = Some(result);
+    });
+    results.into_iter().map(|result| result.expect("every job ran")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn results_come_back_in_order() {
+        let jobs: Vec<Job<usize>> = (0..20)
+            .map(|i| -> Job<usize> {
+                Box::new(move || {
+                    thread::sleep(Duration::from_millis(((20 - i) % 7) as u64));
+                    i * 10
+                })
+            })
+            .collect();
+        let mut seen = Vec::new();
+        run_ordered(jobs, 4, |i, result| seen.push((i, result.unwrap())));
+        assert_eq!((0..20).map(|i| (i, i * 10)).collect::<Vec<_>>(), seen);
+    }
+
+    #[test]
+    fn run_all_reports_every_job() {
+        let jobs: Vec<Job<usize>> = (0..20).map(|i| -> Job<usize> { Box::new(move || i + 1) }).collect();
+        let mut finished = 0;
+        assert_eq!((1..=20).map(Ok).collect::<Vec<_>>(), run_all(jobs, 3, |_, _| finished += 1));
+        assert_eq!(20, finished);
+    }
+
+    #[test]
+    fn panics_are_reported() {
+        let jobs: Vec<Job<usize>> = (0..4)
+            .map(|i| -> Job<usize> { Box::new(move || if i == 2 { panic!("job {} failed", i) } else { i }) })
+            .collect();
+        let results = run_all(jobs, 2, |_, _| {});
+        assert_eq!(vec![Ok(0), Ok(1), Err(String::from("job 2 failed")), Ok(3)], results);
+    }
+}
@@ -0,0 +1,95 @@
+// Disjoint sets over `0..n` with path compression and union by size, for
+// Day 8's circuits of junction boxes.
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self { parent: (0..n).collect(), size: vec![1; n], components: n }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Joins the sets containing `a` and `b`. Returns `false` if they already were one set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    /// The number of disjoint sets.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every set, largest first.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len()).filter(|&x| self.parent[x] == x).map(|x| self.size[x]).collect();
+        sizes.sort_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn joins_sets() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 1));
+        assert!(!sets.union(0, 3));
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_eq!(vec![3, 1, 1], sets.sizes());
+    }
+
+    proptest! {
+        #[test]
+        fn sizes_sum_to_n((n, pairs) in (1usize..40).prop_flat_map(|n| (Just(n), prop::collection::vec((0..n, 0..n), 0..60)))) {
+            let mut sets = UnionFind::new(n);
+            let joined = pairs.iter().filter(|&&(a, b)| sets.union(a, b)).count();
+            let sizes = sets.sizes();
+            prop_assert_eq!(n, sizes.iter().sum::<usize>());
+            prop_assert_eq!(n - joined, sizes.len());
+            prop_assert_eq!(sizes.len(), sets.components());
+            for &(a, b) in &pairs {
+                prop_assert_eq!(sets.find(a), sets.find(b));
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// Puzzle-specific invariants on the raw input. Each day's `validate` lists every
+// violation it finds with its location, rather than stopping at the first like
+// the parsers do; the binaries run it before solving, and `aoc validate` prints it.
+
+use std::error::Error;
+use std::fmt;
+use crate::parse::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Line from 1, or `None` when the input as a whole is at fault.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { line: Some(line), column: Some(column), message: message.into() }
+    }
+
+    pub fn line(line: usize, message: impl Into<String>) -> Self {
+        Self { line: Some(line), column: None, message: message.into() }
+    }
+
+    pub fn input(message: impl Into<String>) -> Self {
+        Self { line: None, column: None, message: message.into() }
+    }
+}
+
+impl From<ParseError> for Violation {
+    fn from(err: ParseError) -> Self {
+        Self::at(err.line, err.column, err.message)
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// An input that breaks at least one invariant.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Invalid(pub Vec<Violation>);
+
+// `main` reports errors with `Debug`, so list the violations rather than the structs.
+impl fmt::Debug for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid input, {} problem{}:", self.0.len(), if self.0.len() == 1 { "" } else { "s" })?;
+        for violation in &self.0 {
+            write!(f, "\n  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Invalid {}
+
+/// Runs a day's `validate` on `input`, failing with every violation it reports.
+pub fn check(input: &str, validate: fn(&str) -> Vec<Violation>) -> Result<(), Invalid> {
+    match validate(input) {
+        violations if violations.is_empty() => Ok(()),
+        violations => Err(Invalid(violations)),
+    }
+}
+
+/// The non-blank lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i + 1, line)).filter(|(_, line)| !line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day04, day07, day09, day11};
+
+    #[test]
+    fn reports_every_violation() {
+        let err = check("@.@\n@x\n..@.\n", day04::validate).unwrap_err();
+        assert_eq!(
+            "invalid input, 3 problems:\n  line 2, column 2: unexpected 'x', expected '@' or '.'\n  \
+             line 2: expected 3 cells, found 2\n  line 3: expected 3 cells, found 4",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn day07_start() {
+        assert_eq!(vec![Violation::line(1, "no 'S' in the first row")], day07::validate("...\n.^.\n"));
+        assert_eq!(
+            vec![Violation::at(1, 3, "more than one 'S' in the first row"), Violation::at(2, 1, "'S' below the first row")],
+            day07::validate(".SS\nS^.\n")
+        );
+    }
+
+    #[test]
+    fn day09_polygon() {
+        assert_eq!(Vec::<Violation>::new(), day09::validate("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n"));
+        let violations = day09::validate("1,1\n3,2\n3,4\n1,4\n");
+        assert_eq!(Some(1), violations[0].line);
+        assert_eq!(Violation::at(2, 3, "expected a number, found 'x'"), day09::validate("1,1\n3,x\n")[0]);
+    }
+
+    #[test]
+    fn day11_loops_and_duplicates() {
+        assert_eq!(
+            vec![
+                Violation::line(3, "device a is already listed on line 1"),
+                Violation::line(1, "the devices loop: a -> b -> a"),
+            ],
+            day11::validate("a: b\nb: a out\na: out\n")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// Frame-by-frame rendering of character grids. In the terminal every frame
+// redraws the screen in colour; headless, frames are written as numbered text
+// files so runs can be inspected or compared afterwards.
+//
+// The binaries read `--visualize` (terminal) or `--visualize=DIR` (headless),
+// `--delay=MS` for the pause between terminal frames, and `--export=PATH` into
+// an `Output`, and hand it to part 2 for the real input only.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    fn ansi(self) -> &'static str {
+        match self {
+            Colour::Grey => "\x1b[90m",
+            Colour::Red => "\x1b[31m",
+            Colour::Green => "\x1b[32m",
+            Colour::Yellow => "\x1b[33m",
+            Colour::Blue => "\x1b[34m",
+            Colour::Magenta => "\x1b[35m",
+            Colour::Cyan => "\x1b[36m",
+            Colour::White => "\x1b[97m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Terminal { delay: Duration },
+    Headless { dir: PathBuf },
+}
+
+/// What part 2 draws besides its answer. The default draws nothing.
+#[derive(Debug, Clone, Default)]
+pub struct Output {
+    pub visualizer: Option<Visualizer>,
+    /// Where to save a picture; see `canvas::export`.
+    pub export: Option<PathBuf>,
+}
+
+impl Output {
+    /// Reads `--visualize`, `--delay` and `--export=PATH` from the command line.
+    pub fn from_args() -> Self {
+        Self {
+            visualizer: Visualizer::from_args(),
+            export: std::env::args().find_map(|arg| arg.strip_prefix("--export=").map(PathBuf::from)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Visualizer {
+    mode: Mode,
+    palette: HashMap<char, Colour>,
+    frames: usize,
+}
+
+impl Visualizer {
+    pub fn new(mode: Mode) -> Self {
+        Self { mode, palette: HashMap::new(), frames: 0 }
+    }
+
+    /// Reads `--visualize[=DIR]` and `--delay=MS` from the command line; `None` without `--visualize`.
+    pub fn from_args() -> Option<Self> {
+        let mut mode = None;
+        let mut delay = Duration::from_millis(100);
+        for arg in std::env::args() {
+            if arg == "--visualize" {
+                mode = mode.or(Some(None));
+            } else if let Some(dir) = arg.strip_prefix("--visualize=") {
+                mode = Some(Some(PathBuf::from(dir)));
+            } else if let Some(ms) = arg.strip_prefix("--delay=").and_then(|ms| ms.parse().ok()) {
+                delay = Duration::from_millis(ms);
+            }
+        }
+        Some(Self::new(match mode? {
+            Some(dir) => Mode::Headless { dir },
+            None => Mode::Terminal { delay },
+        }))
+    }
+
+    /// Colours `cell` wherever it appears. Cells without a colour are drawn plainly.
+    pub fn with_palette(mut self, palette: &[(char, Colour)]) -> Self {
+        self.palette.extend(palette.iter().copied());
+        self
+    }
+
+    /// Number of frames shown so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// The grid as text, one line per row, with ANSI colours if `colour` is set.
+    pub fn render(&self, grid: &[Vec<char>], colour: bool) -> String {
+        let mut out = String::new();
+        for row in grid {
+            for &cell in row {
+                match self.palette.get(&cell) {
+                    Some(c) if colour => {
+                        out.push_str(c.ansi());
+                        out.push(cell);
+                        out.push_str(RESET);
+                    }
+                    _ => out.push(cell),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn show(&mut self, title: &str, grid: &[Vec<char>]) -> io::Result<()> {
+        self.frames += 1;
+        match &self.mode {
+            Mode::Terminal { delay } => {
+                let mut stdout = io::stdout().lock();
+                write!(stdout, "{}{}\n{}", CLEAR, title, self.render(grid, true))?;
+                stdout.flush()?;
+                thread::sleep(*delay);
+            }
+            Mode::Headless { dir } => {
+                fs::create_dir_all(dir)?;
+                let path = dir.join(format!("frame_{:04}.txt", self.frames));
+                fs::write(path, format!("{}\n{}", title, self.render(grid, false)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headless_frames() {
+        let dir = std::env::temp_dir().join(format!("visualize-{}", std::process::id()));
+        let mut vis = Visualizer::new(Mode::Headless { dir: dir.clone() }).with_palette(&[('@', Colour::Yellow)]);
+        let grid = vec![vec!['.', '@'], vec!['@', '.']];
+        assert_eq!(".\x1b[33m@\x1b[0m\n\x1b[33m@\x1b[0m.\n", vis.render(&grid, true));
+
+        vis.show("round 1", &grid).unwrap();
+        vis.show("round 2", &[vec!['.', '.']]).unwrap();
+        assert_eq!(2, vis.frames());
+        assert_eq!("round 1\n.@\n@.\n", fs::read_to_string(dir.join("frame_0001.txt")).unwrap());
+        assert_eq!("round 2\n..\n", fs::read_to_string(dir.join("frame_0002.txt")).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
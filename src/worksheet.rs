@@ -0,0 +1,106 @@
+// Column-aligned worksheets like Day 6's: problems are laid out side by side
+// and separated by columns that are blank on every line. Lines may have
+// different lengths; missing cells read as spaces.
+
+use std::io::{self, BufRead};
+
+/// One problem of a worksheet as a rectangular character matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// Column of the worksheet where the block starts.
+    pub start: usize,
+    cells: Vec<Vec<char>>,
+}
+
+impl Block {
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> char {
+        self.cells[row][col]
+    }
+
+    pub fn row(&self, row: usize) -> String {
+        self.cells[row].iter().collect()
+    }
+
+    pub fn column(&self, col: usize) -> String {
+        self.cells.iter().map(|row| row[col]).collect()
+    }
+
+    /// Rows top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.height()).map(|r| self.row(r))
+    }
+
+    /// Columns read top to bottom, from the rightmost column to the leftmost.
+    pub fn columns_rtl(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.width()).rev().map(|c| self.column(c))
+    }
+
+    /// Splits off the bottom row, e.g. the operator row under a column of numbers.
+    pub fn split_last_row(&self) -> (Block, String) {
+        let mut cells = self.cells.clone();
+        let last = cells.pop().map(|row| row.into_iter().collect()).unwrap_or_default();
+        (Block { start: self.start, cells }, last)
+    }
+}
+
+/// Splits lines into blocks at columns that are blank on every line. Blank lines are ignored.
+pub fn blocks<S: AsRef<str>>(lines: &[S]) -> Vec<Block> {
+    let grid: Vec<Vec<char>> = lines
+        .iter()
+        .map(|line| line.as_ref().trim_end_matches(['\r', '\n']))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let cell = |r: usize, c: usize| grid[r].get(c).copied().unwrap_or(' ');
+    let separator = |c: usize| (0..grid.len()).all(|r| cell(r, c) == ' ');
+
+    let mut blocks = Vec::new();
+    let mut c = 0;
+    while c < width {
+        if separator(c) {
+            c += 1;
+            continue;
+        }
+        let start = c;
+        while c < width && !separator(c) {
+            c += 1;
+        }
+        let cells = (0..grid.len())
+            .map(|r| (start..c).map(|col| cell(r, col)).collect())
+            .collect();
+        blocks.push(Block { start, cells });
+    }
+    blocks
+}
+
+pub fn read_blocks<R: BufRead>(reader: R) -> io::Result<Vec<Block>> {
+    let lines = reader.lines().collect::<io::Result<Vec<String>>>()?;
+    Ok(blocks(&lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ragged_lines_and_separators() {
+        let lines = ["123 328  51", " 45 64  387", "  6", "*   +   *", ""];
+        let blocks = blocks(&lines);
+        assert_eq!(3, blocks.len());
+        assert_eq!(vec![0, 4, 8], blocks.iter().map(|b| b.start).collect::<Vec<_>>());
+
+        let (numbers, op) = blocks[2].split_last_row();
+        assert_eq!("*  ", op);
+        assert_eq!(vec![" 51", "387", "   "], numbers.rows().collect::<Vec<_>>());
+        assert_eq!(vec!["17 ", "58 ", " 3 "], numbers.columns_rtl().collect::<Vec<_>>());
+    }
+}